@@ -1,15 +1,175 @@
 /* auto-generated by NAPI-RS */
 /* eslint-disable */
+/**
+ * A running ChatGPT browser login. Show `auth_url` to the user, then await
+ * `wait_for_completion`, which resolves once `auth.json` has been written.
+ */
+export declare class ChatGptLogin {
+  get authUrl(): string
+  get port(): number
+  waitForCompletion(): Promise<void>
+  /** Stops the callback server; a pending `wait_for_completion` rejects. */
+  cancel(): void
+}
+
+/**
+ * Long-lived cloud tasks handle. The backend and ChatGPT headers are built on
+ * first use and, with CLI-managed tokens, reused until the token expires or a
+ * request comes back 401; the tokens are then refreshed and both are rebuilt,
+ * retrying the failed request once (for `watch`, each poll). An explicit
+ * `bearerToken` is never refreshed, so its 401 reaches the caller. Requests
+ * this binding sends itself (task list pages, environments, task actions)
+ * share one connection pool; those sent through the `CloudBackend` use the
+ * backend's own. In mock mode, archiving and cancelling are remembered by
+ * this client for its own `list`, `getText` and `watch`.
+ */
+export declare class CloudTasksClient {
+  constructor(config: CloudTasksConfig)
+  list(environmentId?: string | undefined | null): Promise<Array<TaskSummaryNapi>>
+  listPage(opts?: ListTasksOptionsNapi | undefined | null): Promise<TaskListPageNapi>
+  listEnvironments(): Promise<Array<EnvironmentRowNapi>>
+  create(opts: CreateTaskOptionsNapi): Promise<string>
+  cancel(taskId: string): Promise<void>
+  archive(taskId: string): Promise<void>
+  unarchive(taskId: string): Promise<void>
+  getDiff(taskId: string): Promise<string | null>
+  getMessages(taskId: string): Promise<Array<string>>
+  getText(taskId: string): Promise<TaskTextNapi>
+  listAttempts(taskId: string, turnId: string): Promise<Array<TurnAttemptNapi>>
+  apply(taskId: string, diffOverride: string | undefined | null, preflight: boolean): Promise<ApplyOutcomeNapi>
+  watch(taskId: string, opts: WatchTaskOptionsNapi | undefined | null, callback: ((err: Error | null, arg: TaskWatchEventNapi) => void)): Promise<TaskTextNapi>
+}
+
 export declare class CodexSession {
   get conversationId(): string
   nextEvent(): Promise<string | null>
-  submit(submissionJson: string): Promise<void>
-  close(): Promise<void>
+  /**
+   * Drains up to `max` available events in a single call. If none are ready,
+   * waits up to `timeout_ms` for the first one (indefinitely when omitted).
+   * Resolves to `null` once the event stream is closed.
+   */
+  nextEvents(max: number, timeoutMs?: number | undefined | null): Promise<Array<string> | null>
+  /**
+   * Like `next_event`, but hands the event over as a JS object instead of a
+   * JSON string, so callers do not need `JSON.parse`.
+   */
+  nextEventObject(): Promise<CodexEventObject | null>
+  /**
+   * Pushes every event to `callback` as `(err, eventJson)` from a background
+   * task instead of requiring a JS poll loop. If `callback` returns a Promise,
+   * it is awaited before the next event is read, so an async callback applies
+   * backpressure. `eventJson` is `null` when the stream closes. Resolves when
+   * the stream closes or `unsubscribe` is called; if `callback` throws or its
+   * Promise rejects, the subscription stops and this rejects with that error.
+   * Do not mix with `next_event` polling on the same session.
+   */
+  subscribe(callback: (err: Error | null, eventJson: string | null) => void | Promise<void>): Promise<void>
+  /** Stops a subscription started with `subscribe`; a no-op if there is none. */
+  unsubscribe(): void
+  /** Submits a full `Submission` JSON and resolves to its id. */
+  submit(submissionJson: string): Promise<string>
+  /** Submits a bare `Op` JSON; the id is generated natively and returned. */
+  submitOp(opJson: string): Promise<string>
+  /** Sends a user turn built from `items`. Resolves to the submission id. */
+  submitUserInput(items: Array<UserInputItem>): Promise<string>
+  interrupt(): Promise<string>
+  /** `decision` is one of `approved`, `approved_for_session`, `denied` or `abort`. */
+  approveExec(id: string, decision: string): Promise<string>
+  /** `decision` is one of `approved`, `approved_for_session`, `denied` or `abort`. */
+  approvePatch(id: string, decision: string): Promise<string>
+  compact(): Promise<string>
+  shutdown(): Promise<string>
+  /** The config this session was started with, secrets redacted. */
+  config(): EffectiveConfig
+  get rolloutPath(): string
+  /**
+   * Forks this conversation into a new one whose history is truncated
+   * before the `nth_user_message`-th user message (zero-based).
+   */
+  fork(nthUserMessage: number): Promise<CodexSession>
+  /**
+   * Removes the conversation from the manager. With `graceful`, first submits
+   * `Op::Shutdown` and waits up to `timeout_ms` for `ShutdownComplete` so
+   * in-flight work and the rollout file can finish; events seen meanwhile stay
+   * buffered for `next_event`.
+   */
+  close(options?: CloseOptions | undefined | null): Promise<void>
 }
 
 export declare class NativeCodex {
   constructor(options?: NativeCodexOptions | undefined | null)
   createConversation(options?: CreateConversationOptions | undefined | null): Promise<CodexSession>
+  /**
+   * Resumes a conversation from a rollout file written by a previous session.
+   * The first pending event is the resumed `SessionConfigured`, which carries
+   * the replayed history in `initial_messages`.
+   */
+  resumeConversation(rolloutPath: string, options?: CreateConversationOptions | undefined | null): Promise<CodexSession>
+  /**
+   * Starts a new conversation from the rollout at `rollout_path`, keeping
+   * only the history before the `nth_user_message`-th user message.
+   */
+  forkConversation(rolloutPath: string, nthUserMessage: number, options?: CreateConversationOptions | undefined | null): Promise<CodexSession>
+  /**
+   * Loads the config a conversation would get with `options` without
+   * starting one. Secrets are redacted.
+   */
+  resolveConfig(options?: CreateConversationOptions | undefined | null): Promise<EffectiveConfig>
+  /**
+   * `apikey` or `chatgpt` for the credentials in this instance's codex_home,
+   * or `null` when signed out.
+   */
+  getAuthMode(): string | null
+  authStatus(): Promise<AuthStatus>
+  /** Writes `api_key` to this instance's `auth.json` and reloads credentials. */
+  loginWithApiKey(apiKey: string): Promise<void>
+  /**
+   * Refreshes the ChatGPT tokens now instead of waiting for a failed turn, and
+   * resolves to the updated status.
+   */
+  refreshAuth(): Promise<AuthStatus>
+  /**
+   * Re-reads `auth.json`, e.g. after the CLI logged in. Resolves to `true`
+   * when the credentials changed.
+   */
+  reloadAuth(): Promise<boolean>
+  /**
+   * Calls `callback` with the new `AuthStatus` whenever this instance logs in,
+   * logs out, reloads or refreshes credentials. Changes made outside the SDK,
+   * such as a token refresh inside codex-core, are picked up by a background
+   * check every 30 seconds. The callback does not keep the process alive.
+   * Returns an id for `off_auth_change`.
+   */
+  onAuthChange(callback: ((err: Error | null, arg: AuthStatus) => void)): number
+  /**
+   * Removes a callback registered with `on_auth_change`. Returns `false` if
+   * the id is unknown.
+   */
+  offAuthChange(id: number): boolean
+  /**
+   * Starts the ChatGPT browser login callback server on a local port and returns
+   * a handle with the authorization URL. Credentials are written to this
+   * instance's codex_home. The device-code flow is not available in the
+   * pinned codex-login.
+   */
+  startChatgptLogin(options?: ChatGptLoginOptions | undefined | null): Promise<ChatGptLogin>
+  /** Removes stored credentials. Resolves to `false` if there were none. */
+  logout(): Promise<boolean>
+  /**
+   * Pages through the rollout files under this instance's codex_home, newest
+   * first.
+   */
+  listSessions(options?: ListSessionsOptions | undefined | null): Promise<SessionsPage>
+  /**
+   * Ids of the conversations opened through this instance that have not been
+   * closed, shut down or seen their event stream end.
+   */
+  listConversations(): Array<string>
+  /**
+   * Returns a new handle to a live conversation, sharing its buffered events
+   * and subscription with any other handle.
+   */
+  getConversation(conversationId: string): CodexSession | null
 }
 
 export interface ApplyOutcomeNapi {
@@ -20,10 +180,36 @@ export interface ApplyOutcomeNapi {
   conflictPaths: Array<string>
 }
 
+export interface AuthStatus {
+  /** `apikey` or `chatgpt`; absent when signed out. */
+  authMode?: string
+  accountId?: string
+  planType?: string
+  email?: string
+  /** Expiry of the ChatGPT access token, as an RFC 3339 timestamp. */
+  tokenExpiresAt?: string
+}
+
+export interface ChatGptLoginOptions {
+  /** Local port for the OAuth callback server; defaults to codex-login's port (1455). */
+  port?: number
+  /**
+   * Open the system browser on the authorization URL. Defaults to `false`,
+   * since the SDK usually runs headless and relays the URL to a web UI.
+   */
+  openBrowser?: boolean
+}
+
 export declare function cliVersion(): string
 
 export declare function cloudTasksApply(config: CloudTasksConfig, taskId: string, diffOverride: string | undefined | null, preflight: boolean): Promise<ApplyOutcomeNapi>
 
+/** Hides a task from the default (`current`) task list. */
+export declare function cloudTasksArchive(config: CloudTasksConfig, taskId: string): Promise<void>
+
+/** Stops a running task's in-progress attempts. */
+export declare function cloudTasksCancel(config: CloudTasksConfig, taskId: string): Promise<void>
+
 export interface CloudTasksConfig {
   baseUrl: string
   bearerToken?: string
@@ -41,19 +227,79 @@ export declare function cloudTasksGetMessages(config: CloudTasksConfig, taskId:
 
 export declare function cloudTasksGetText(config: CloudTasksConfig, taskId: string): Promise<TaskTextNapi>
 
+/** First page of active tasks; see `cloud_tasks_list_page` for filters and paging. */
 export declare function cloudTasksList(config: CloudTasksConfig, environmentId?: string | undefined | null): Promise<Array<TaskSummaryNapi>>
 
 export declare function cloudTasksListAttempts(config: CloudTasksConfig, taskId: string, turnId: string): Promise<Array<TurnAttemptNapi>>
 
 export declare function cloudTasksListEnvironments(config: CloudTasksConfig): Promise<Array<EnvironmentRowNapi>>
 
+export declare function cloudTasksListPage(config: CloudTasksConfig, opts?: ListTasksOptionsNapi | undefined | null): Promise<TaskListPageNapi>
+
+/** Moves an archived task back to the default (`current`) task list. */
+export declare function cloudTasksUnarchive(config: CloudTasksConfig, taskId: string): Promise<void>
+
+/**
+ * Polls `task_id` until every best-of-N attempt has completed, failed or been
+ * cancelled and resolves with the task's final text. The first poll reports the current
+ * state; later events are only sent for what changed. Rejects with `timeout`,
+ * `unauthorized` when a poll gets a 401, or `cloud_request_failed` when it fails
+ * otherwise.
+ */
+export declare function cloudTasksWatch(config: CloudTasksConfig, taskId: string, opts: WatchTaskOptionsNapi | undefined | null, callback: ((err: Error | null, arg: TaskWatchEventNapi) => void)): Promise<TaskTextNapi>
+
+export interface CloseOptions {
+  graceful?: boolean
+  timeoutMs?: number
+}
+
+/**
+ * An `Event` as `{ id, type, msg }`, where `type` is the `EventMsg` tag (e.g.
+ * `agent_message_delta`). `msg` is written straight into JS values by napi's
+ * serde serializer, with no JSON string or `serde_json::Value` in between.
+ */
+export interface CodexEventObject {
+  id: string
+  type: string
+  msg: EventMsg
+}
+
+/** A `compacted` rollout record: the summary that replaced earlier history. */
+export interface CompactedItem {
+  message: string
+}
+
 export interface ConfigOverrideEntry {
   key: string
   value: string
 }
 
+/** Content of a `message` response item. */
+export type ContentItem =
+  | { type: 'input_text'; text: string }
+  | { type: 'input_image'; image_url: string }
+  | { type: 'output_text'; text: string }
+
 export interface CreateConversationOptions {
   overrides?: Array<ConfigOverrideEntry>
+  /**
+   * Reject overrides with unknown keys or values that do not fit the config
+   * schema instead of passing them through; the error lists every bad entry.
+   */
+  strictOverrides?: boolean
+  model?: string
+  cwd?: string
+  /** One of `untrusted`, `on-failure`, `on-request` or `never`. */
+  approvalPolicy?: string
+  /** One of `read-only`, `workspace-write` or `danger-full-access`. */
+  sandboxMode?: string
+  modelProvider?: string
+  configProfile?: string
+  baseInstructions?: string
+  includePlanTool?: boolean
+  includeApplyPatchTool?: boolean
+  includeViewImageTool?: boolean
+  showRawAgentReasoning?: boolean
 }
 
 export interface CreateTaskOptionsNapi {
@@ -70,6 +316,19 @@ export interface DiffSummaryNapi {
   linesRemoved: number
 }
 
+export interface EffectiveConfig {
+  model: string
+  modelProviderId: string
+  modelProvider: any
+  modelReasoningEffort?: string
+  approvalPolicy: string
+  sandboxPolicy: any
+  cwd: string
+  codexHome: string
+  mcpServers: any
+  activeProfile?: string
+}
+
 export interface EnvironmentRowNapi {
   id: string
   label?: string
@@ -77,8 +336,51 @@ export interface EnvironmentRowNapi {
   repoHints?: string
 }
 
+/** A serialized `EventMsg`; the other fields depend on `type`. */
+export interface EventMsg {
+  type: string
+  [key: string]: unknown
+}
+
+export interface ListSessionsOptions {
+  pageSize?: number
+  /** Opaque `next_cursor` from a previous page. */
+  cursor?: string
+  /**
+   * Only include sessions from these sources (`cli`, `vscode`, `exec`, `mcp`);
+   * all sources when omitted.
+   */
+  sources?: Array<string>
+}
+
+export interface ListTasksOptionsNapi {
+  environmentId?: string
+  /** Opaque `next_cursor` from a previous page. */
+  cursor?: string
+  /** Page size requested from the backend; defaults to 20. */
+  limit?: number
+  /** Only include tasks in these states (`pending`, `ready`, `applied`, `error`). */
+  statuses?: Array<string>
+  /** `true` lists archived tasks only, `false` active tasks only; defaults to active. */
+  archived?: boolean
+  /** Only include review tasks (`true`) or non-review tasks (`false`). */
+  isReview?: boolean
+  /**
+   * RFC 3339 timestamp; only include tasks updated at or after it. The list
+   * is newest first, so paging stops at the first older task.
+   */
+  updatedSince?: string
+}
+
 export interface NativeCodexOptions {
   codexHome?: string
+  /** Recorded in rollouts; one of `cli`, `vscode`, `exec`, `mcp` (default) or `unknown`. */
+  sessionSource?: string
+  /**
+   * Originator reported to the server. codex-core keeps a single originator per
+   * process, so a value that differs from an earlier instance's is rejected.
+   */
+  originator?: string
 }
 
 export interface PullRequestNapi {
@@ -95,6 +397,84 @@ export interface PullRequestNapi {
   mergeCommitSha?: string
 }
 
+/**
+ * Parses a rollout JSONL file with the pinned `codex_protocol` types, so the
+ * format always matches the codex-rs version this module was built against.
+ * Rejects with `io` when the file cannot be read and `invalid_rollout` (with
+ * the 1-based `line` in `details`) when a record does not parse.
+ */
+export declare function readRollout(path: string): Promise<RolloutContents>
+
+/** A serialized `ResponseItem`, discriminated by `type`. */
+export type ResponseItem =
+  | { type: 'message'; id?: string; role: string; content: Array<ContentItem> }
+  | {
+      type: 'reasoning'
+      id: string
+      summary: Array<{ type: 'summary_text'; text: string }>
+      content?: Array<{ type: 'reasoning_text' | 'text'; text: string }>
+      encrypted_content?: string
+    }
+  | { type: 'local_shell_call'; id?: string; call_id?: string; status: string; action: unknown }
+  | { type: 'function_call'; id?: string; name: string; arguments: string; call_id: string }
+  | { type: 'function_call_output'; call_id: string; output: string }
+  | { type: 'custom_tool_call'; id?: string; status?: string; call_id: string; name: string; input: string }
+  | { type: 'custom_tool_call_output'; call_id: string; output: string }
+  | { type: 'web_search_call'; id?: string; status?: string; action: unknown }
+  | { type: 'other' }
+
+/**
+ * Rollout records after the session meta, split by kind. Each list keeps file
+ * order; the payloads are the `codex_protocol` types serialized as-is.
+ */
+export interface RolloutContents {
+  sessionMeta?: RolloutSessionMeta
+  responseItems: Array<ResponseItem>
+  eventMsgs: Array<EventMsg>
+  turnContexts: Array<TurnContextItem>
+  compacted: Array<CompactedItem>
+}
+
+export interface RolloutSessionMeta {
+  id: string
+  timestamp: string
+  cwd: string
+  originator: string
+  cliVersion: string
+  instructions?: string
+  source?: string
+  git?: any
+}
+
+export interface SessionsPage {
+  sessions: Array<SessionSummary>
+  nextCursor?: string
+  /** True when the scan stopped early; more sessions may exist past `next_cursor`. */
+  reachedScanCap: boolean
+}
+
+export interface SessionSummary {
+  sessionId?: string
+  createdAt?: string
+  updatedAt?: string
+  cwd?: string
+  source?: string
+  /** First user message, truncated. */
+  preview?: string
+  rolloutPath: string
+}
+
+/**
+ * Filters other than `environment_id` and `archived` are applied to each page
+ * after it is fetched, so a page may hold fewer than `limit` tasks while
+ * `next_cursor` is still set. `next_cursor` is cleared once the page reaches a
+ * task older than `updated_since`.
+ */
+export interface TaskListPageNapi {
+  tasks: Array<TaskSummaryNapi>
+  nextCursor?: string
+}
+
 export interface TaskSummaryNapi {
   id: string
   title: string
@@ -128,6 +508,22 @@ export interface TaskTextNapi {
   attemptStatus?: string
 }
 
+export interface TaskWatchEventNapi {
+  /** `status`, `attempt` or `diff_summary`. */
+  type: string
+  taskId: string
+  /** Attempt the event refers to; absent on `status` events. */
+  turnId?: string
+  attemptPlacement?: number
+  /**
+   * Task status (`pending`, `ready`, `error`) on `status` events, attempt
+   * status on `attempt` events.
+   */
+  status?: string
+  /** Diff stats of the task's own attempt, on `diff_summary` events. */
+  summary?: DiffSummaryNapi
+}
+
 export interface TurnAttemptNapi {
   turnId: string
   attemptPlacement?: number
@@ -137,4 +533,39 @@ export interface TurnAttemptNapi {
   messages: Array<string>
 }
 
+/** A `turn_context` rollout record: the settings a turn ran with. */
+export interface TurnContextItem {
+  cwd: string
+  approval_policy: string
+  sandbox_policy: { mode: string; [key: string]: unknown }
+  model: string
+  effort?: string
+  summary: string
+}
+
+export interface UserInputItem {
+  /** One of `text`, `image` or `local_image`. */
+  type: string
+  text?: string
+  imageUrl?: string
+  path?: string
+}
+
 export declare function version(): string
+
+export interface WatchTaskOptionsNapi {
+  /**
+   * Delay between polls while the task keeps changing; defaults to 2000 ms.
+   * Doubles after each poll without changes, up to 30 s.
+   */
+  intervalMs?: number
+  /** Rejects with a `timeout` error if the attempts have not settled by then. */
+  timeoutMs?: number
+  /**
+   * Number of attempts to wait for, i.e. the `best_of_n` the task was created
+   * with; defaults to 1. Only used until the task lists its sibling attempts,
+   * which it does not right after creation. If the first attempt has finished
+   * and the next poll still lists none, the task is taken to have one attempt.
+   */
+  attemptTotal?: number
+}
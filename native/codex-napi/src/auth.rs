@@ -0,0 +1,217 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::Duration;
+
+use base64::Engine as _;
+use codex_core::{AuthManager, CodexAuth};
+use napi::threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use napi_derive::napi;
+use serde_json::Value as JsonValue;
+use tokio::task::JoinHandle;
+
+/// Weak so that a registered listener does not keep the Node event loop alive.
+type AuthListener = ThreadsafeFunction<AuthStatus, (), AuthStatus, napi::Status, true, true>;
+
+/// How often the watcher re-reads credentials to catch changes made outside
+/// the SDK (token refreshes inside codex-core, `codex login` in another process).
+const AUTH_WATCH_INTERVAL: Duration = Duration::from_secs(30);
+
+#[napi(object)]
+#[derive(Clone, PartialEq)]
+pub struct AuthStatus {
+    /// `apikey` or `chatgpt`; absent when signed out.
+    pub auth_mode: Option<String>,
+    pub account_id: Option<String>,
+    pub plan_type: Option<String>,
+    pub email: Option<String>,
+    /// Expiry of the ChatGPT access token, as an RFC 3339 timestamp.
+    pub token_expires_at: Option<String>,
+}
+
+/// Wraps an instance's `AuthManager` so that every reload, refresh, login and
+/// logout done through the SDK reaches listeners registered with `on_auth_change`.
+/// While listeners are registered, a background watcher also polls the
+/// credentials and notifies when the status differs from the last one seen.
+#[derive(Clone)]
+pub(crate) struct AuthNotifier {
+    state: Arc<NotifierState>,
+}
+
+struct NotifierState {
+    auth_manager: Arc<AuthManager>,
+    listeners: Mutex<HashMap<u32, AuthListener>>,
+    next_id: AtomicU32,
+    last_status: Mutex<Option<AuthStatus>>,
+    watcher: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl AuthNotifier {
+    pub(crate) fn new(auth_manager: Arc<AuthManager>) -> Self {
+        Self {
+            state: Arc::new(NotifierState {
+                auth_manager,
+                listeners: Mutex::default(),
+                next_id: AtomicU32::new(1),
+                last_status: Mutex::default(),
+                watcher: Mutex::default(),
+            }),
+        }
+    }
+
+    /// Registers `listener` and returns an id for `unsubscribe`. Starts the
+    /// watcher if it is not already running.
+    pub(crate) fn subscribe(&self, listener: AuthListener) -> u32 {
+        let id = self.state.next_id.fetch_add(1, Ordering::Relaxed);
+        self.state.listeners.lock().unwrap().insert(id, listener);
+
+        let mut watcher = self.state.watcher.lock().unwrap();
+        if watcher.as_ref().is_none_or(|handle| handle.is_finished()) {
+            *watcher = Some(napi::bindgen_prelude::spawn(watch_auth(Arc::downgrade(
+                &self.state,
+            ))));
+        }
+        id
+    }
+
+    /// Removes a listener. The watcher stops once no listeners remain.
+    pub(crate) fn unsubscribe(&self, id: u32) -> bool {
+        let mut listeners = self.state.listeners.lock().unwrap();
+        let removed = listeners.remove(&id).is_some();
+        if listeners.is_empty() {
+            self.state.stop_watcher();
+        }
+        removed
+    }
+
+    /// Re-reads `auth.json` and notifies listeners when the credentials changed.
+    pub(crate) async fn reload(&self) -> bool {
+        let changed = self.state.auth_manager.reload();
+        if changed {
+            self.notify().await;
+        }
+        changed
+    }
+
+    pub(crate) async fn notify(&self) {
+        let status = auth_status(self.state.auth_manager.auth()).await;
+        self.state.publish(status, true);
+    }
+}
+
+impl NotifierState {
+    /// Records `status` as the last one seen and sends it to the listeners,
+    /// either unconditionally or only when it differs from the previous one.
+    /// Listeners whose JS function has been released are dropped.
+    fn publish(&self, status: AuthStatus, force: bool) {
+        let previous = self.last_status.lock().unwrap().replace(status.clone());
+        let changed = previous.as_ref().is_some_and(|previous| *previous != status);
+        if !force && !changed {
+            return;
+        }
+
+        let mut listeners = self.listeners.lock().unwrap();
+        listeners.retain(|_, listener| {
+            listener.call(Ok(status.clone()), ThreadsafeFunctionCallMode::NonBlocking)
+                != napi::Status::Closing
+        });
+        if listeners.is_empty() {
+            self.stop_watcher();
+        }
+    }
+
+    fn stop_watcher(&self) {
+        if let Some(handle) = self.watcher.lock().unwrap().take() {
+            handle.abort();
+        }
+    }
+}
+
+impl Drop for NotifierState {
+    fn drop(&mut self) {
+        self.stop_watcher();
+    }
+}
+
+/// Polls the credentials until the notifier is dropped or the task is aborted.
+/// The first pass only records a baseline.
+async fn watch_auth(state: Weak<NotifierState>) {
+    let mut interval = tokio::time::interval(AUTH_WATCH_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        interval.tick().await;
+        let Some(state) = state.upgrade() else {
+            break;
+        };
+        state.auth_manager.reload();
+        let status = auth_status(state.auth_manager.auth()).await;
+        state.publish(status, false);
+    }
+}
+
+pub(crate) fn auth_mode_name(auth: &CodexAuth) -> Option<String> {
+    serde_json::to_value(&auth.mode)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+}
+
+pub(crate) async fn auth_status(auth: Option<CodexAuth>) -> AuthStatus {
+    let Some(auth) = auth else {
+        return AuthStatus {
+            auth_mode: None,
+            account_id: None,
+            plan_type: None,
+            email: None,
+            token_expires_at: None,
+        };
+    };
+
+    // API-key auth has no token data; ChatGPT auth may fail to load it if auth.json is stale.
+    let token_data = auth.get_token_data().await.ok();
+    AuthStatus {
+        auth_mode: auth_mode_name(&auth),
+        account_id: auth.get_account_id(),
+        plan_type: auth
+            .get_plan_type()
+            .and_then(|plan| serde_json::to_value(plan).ok())
+            .and_then(|value| value.as_str().map(str::to_string)),
+        email: token_data.as_ref().and_then(|t| t.id_token.email.clone()),
+        token_expires_at: token_data
+            .as_ref()
+            .and_then(|t| jwt_expiry(&t.access_token))
+            .and_then(|exp| chrono::DateTime::from_timestamp(exp, 0))
+            .map(|dt| dt.to_rfc3339()),
+    }
+}
+
+pub(crate) fn jwt_expiry(token: &str) -> Option<i64> {
+    let payload_b64 = token.split('.').nth(1).filter(|p| !p.is_empty())?;
+    let payload_bytes = base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(payload_b64.as_bytes())
+        .ok()?;
+    let v: JsonValue = serde_json::from_slice(&payload_bytes).ok()?;
+    v.get("exp").and_then(|exp| exp.as_i64())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jwt(payload: &str) -> String {
+        let encode = |part: &str| base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(part);
+        format!("{}.{}.sig", encode(r#"{"alg":"none"}"#), encode(payload))
+    }
+
+    #[test]
+    fn reads_exp_claim() {
+        assert_eq!(jwt_expiry(&jwt(r#"{"exp":1760000000,"sub":"user"}"#)), Some(1760000000));
+    }
+
+    #[test]
+    fn ignores_tokens_without_exp() {
+        assert_eq!(jwt_expiry(&jwt(r#"{"sub":"user"}"#)), None);
+        assert_eq!(jwt_expiry(&jwt("not json")), None);
+        assert_eq!(jwt_expiry("opaque-token"), None);
+        assert_eq!(jwt_expiry("header..sig"), None);
+    }
+}
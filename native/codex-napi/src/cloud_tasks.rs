@@ -1,3 +1,8 @@
+use std::collections::BTreeSet;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use napi::threadsafe_function::ThreadsafeFunction;
 use napi::Result;
 use napi_derive::napi;
 
@@ -10,7 +15,11 @@ use base64::Engine as _;
 use reqwest::header::{HeaderMap, AUTHORIZATION, USER_AGENT};
 use reqwest::header::HeaderName;
 
+use crate::auth::jwt_expiry;
+use crate::error::native_error;
+
 #[napi(object)]
+#[derive(Clone)]
 pub struct CloudTasksConfig {
     pub base_url: String,
     pub bearer_token: Option<String>,
@@ -113,14 +122,94 @@ pub struct TurnAttemptNapi {
     pub messages: Vec<String>,
 }
 
+#[napi(object)]
+#[derive(Default)]
+pub struct ListTasksOptionsNapi {
+    pub environment_id: Option<String>,
+    /// Opaque `next_cursor` from a previous page.
+    pub cursor: Option<String>,
+    /// Page size requested from the backend; defaults to 20.
+    pub limit: Option<u32>,
+    /// Only include tasks in these states (`pending`, `ready`, `applied`, `error`).
+    pub statuses: Option<Vec<String>>,
+    /// `true` lists archived tasks only, `false` active tasks only; defaults to active.
+    pub archived: Option<bool>,
+    /// Only include review tasks (`true`) or non-review tasks (`false`).
+    pub is_review: Option<bool>,
+    /// RFC 3339 timestamp; only include tasks updated at or after it. The list
+    /// is newest first, so paging stops at the first older task.
+    pub updated_since: Option<String>,
+}
+
+/// Filters other than `environment_id` and `archived` are applied to each page
+/// after it is fetched, so a page may hold fewer than `limit` tasks while
+/// `next_cursor` is still set. `next_cursor` is cleared once the page reaches a
+/// task older than `updated_since`.
+#[napi(object)]
+pub struct TaskListPageNapi {
+    pub tasks: Vec<TaskSummaryNapi>,
+    pub next_cursor: Option<String>,
+}
+
+#[napi(object)]
+#[derive(Default)]
+pub struct WatchTaskOptionsNapi {
+    /// Delay between polls while the task keeps changing; defaults to 2000 ms.
+    /// Doubles after each poll without changes, up to 30 s.
+    pub interval_ms: Option<u32>,
+    /// Rejects with a `timeout` error if the attempts have not settled by then.
+    pub timeout_ms: Option<u32>,
+    /// Number of attempts to wait for, i.e. the `best_of_n` the task was created
+    /// with; defaults to 1. Only used until the task lists its sibling attempts,
+    /// which it does not right after creation. If the first attempt has finished
+    /// and the next poll still lists none, the task is taken to have one attempt.
+    pub attempt_total: Option<u32>,
+}
+
+#[napi(object)]
+pub struct TaskWatchEventNapi {
+    /// `status`, `attempt` or `diff_summary`.
+    #[napi(js_name = "type")]
+    pub event_type: String,
+    pub task_id: String,
+    /// Attempt the event refers to; absent on `status` events.
+    pub turn_id: Option<String>,
+    pub attempt_placement: Option<u32>,
+    /// Task status (`pending`, `ready`, `error`) on `status` events, attempt
+    /// status on `attempt` events.
+    pub status: Option<String>,
+    /// Diff stats of the task's own attempt, on `diff_summary` events.
+    pub summary: Option<DiffSummaryNapi>,
+}
+
+/// First page of active tasks; see `cloud_tasks_list_page` for filters and paging.
 #[napi]
 pub async fn cloud_tasks_list(config: CloudTasksConfig, environment_id: Option<String>) -> Result<Vec<TaskSummaryNapi>> {
-    let backend = create_backend(config).await.map_err(to_napi_error)?;
-    let tasks = backend
-        .list_tasks(environment_id.as_deref())
+    let opts = ListTasksOptionsNapi {
+        environment_id,
+        ..Default::default()
+    };
+    Ok(cloud_tasks_list_page(config, Some(opts)).await?.tasks)
+}
+
+#[napi]
+pub async fn cloud_tasks_list_page(
+    config: CloudTasksConfig,
+    opts: Option<ListTasksOptionsNapi>,
+) -> Result<TaskListPageNapi> {
+    let opts = opts.unwrap_or_default();
+    if config.mock.unwrap_or(false) {
+        let backend = create_backend(config).await.map_err(to_napi_error)?;
+        return list_mock_task_page(backend.as_ref(), &MockTaskState::default(), opts).await;
+    }
+    let filter = TaskFilter::new(&opts)?;
+    let base_url = normalize_base_url(&config.base_url);
+    let headers = build_chatgpt_headers(&config).await;
+    let client = reqwest::Client::builder().build().map_err(to_napi_error)?;
+    let body = fetch_task_list(&client, &base_url, &headers, &task_list_query(opts))
         .await
         .map_err(to_napi_error)?;
-    Ok(tasks.into_iter().map(to_task_summary_napi).collect())
+    Ok(to_task_list_page(&body, &filter))
 }
 
 #[napi]
@@ -128,7 +217,16 @@ pub async fn cloud_tasks_list_environments(config: CloudTasksConfig) -> Result<V
     let base_url = normalize_base_url(&config.base_url);
     let headers = build_chatgpt_headers(&config).await;
     let client = reqwest::Client::builder().build().map_err(to_napi_error)?;
+    list_environments(&client, &base_url, &headers).await.map_err(to_napi_error)
+}
 
+/// Both lookups are best effort, except that a 401 fails the call so an
+/// expired token is not mistaken for an empty environment list.
+async fn list_environments(
+    client: &reqwest::Client,
+    base_url: &str,
+    headers: &HeaderMap,
+) -> anyhow::Result<Vec<EnvironmentRowNapi>> {
     let mut map: std::collections::HashMap<String, EnvironmentRowNapi> = std::collections::HashMap::new();
 
     // 1) Try by-repo for each parsed GitHub origin
@@ -139,17 +237,20 @@ pub async fn cloud_tasks_list_environments(config: CloudTasksConfig) -> Result<V
             } else {
                 format!("{}/api/codex/environments/by-repo/github/{}/{}", base_url, owner, repo)
             };
-            if let Ok(list) = get_envs(&client, &url, &headers).await {
-                for e in list {
-                    let entry = map.entry(e.id.clone()).or_insert(EnvironmentRowNapi {
-                        id: e.id.clone(),
-                        label: e.label.clone(),
-                        is_pinned: e.is_pinned,
-                        repo_hints: Some(format!("{}/{}", owner, repo)),
-                    });
-                    if entry.label.is_none() { entry.label = e.label.clone(); }
-                    if let Some(pin) = e.is_pinned { entry.is_pinned = Some(entry.is_pinned.unwrap_or(false) || pin); }
-                }
+            let list = match get_envs(client, &url, headers).await {
+                Ok(list) => list,
+                Err(e) if is_unauthorized(&e) => return Err(e),
+                Err(_) => continue,
+            };
+            for e in list {
+                let entry = map.entry(e.id.clone()).or_insert(EnvironmentRowNapi {
+                    id: e.id.clone(),
+                    label: e.label.clone(),
+                    is_pinned: e.is_pinned,
+                    repo_hints: Some(format!("{}/{}", owner, repo)),
+                });
+                if entry.label.is_none() { entry.label = e.label.clone(); }
+                if let Some(pin) = e.is_pinned { entry.is_pinned = Some(entry.is_pinned.unwrap_or(false) || pin); }
             }
         }
     }
@@ -158,17 +259,21 @@ pub async fn cloud_tasks_list_environments(config: CloudTasksConfig) -> Result<V
     let list_url = if base_url.contains("/backend-api") {
         format!("{}/wham/environments", base_url)
     } else { format!("{}/api/codex/environments", base_url) };
-    if let Ok(list) = get_envs(&client, &list_url, &headers).await {
-        for e in list {
-            let entry = map.entry(e.id.clone()).or_insert(EnvironmentRowNapi {
-                id: e.id.clone(),
-                label: e.label.clone(),
-                is_pinned: e.is_pinned,
-                repo_hints: None,
-            });
-            if entry.label.is_none() { entry.label = e.label.clone(); }
-            if let Some(pin) = e.is_pinned { entry.is_pinned = Some(entry.is_pinned.unwrap_or(false) || pin); }
+    match get_envs(client, &list_url, headers).await {
+        Ok(list) => {
+            for e in list {
+                let entry = map.entry(e.id.clone()).or_insert(EnvironmentRowNapi {
+                    id: e.id.clone(),
+                    label: e.label.clone(),
+                    is_pinned: e.is_pinned,
+                    repo_hints: None,
+                });
+                if entry.label.is_none() { entry.label = e.label.clone(); }
+                if let Some(pin) = e.is_pinned { entry.is_pinned = Some(entry.is_pinned.unwrap_or(false) || pin); }
+            }
         }
+        Err(e) if is_unauthorized(&e) => return Err(e),
+        Err(_) => {}
     }
 
     // Sort: pinned first, then label (ci), then id
@@ -204,6 +309,24 @@ pub async fn cloud_tasks_create(config: CloudTasksConfig, opts: CreateTaskOption
     Ok(created.id.0)
 }
 
+/// Stops a running task's in-progress attempts.
+#[napi]
+pub async fn cloud_tasks_cancel(config: CloudTasksConfig, task_id: String) -> Result<()> {
+    post_task_action_with_config(&config, &task_id, "cancel").await
+}
+
+/// Hides a task from the default (`current`) task list.
+#[napi]
+pub async fn cloud_tasks_archive(config: CloudTasksConfig, task_id: String) -> Result<()> {
+    post_task_action_with_config(&config, &task_id, "archive").await
+}
+
+/// Moves an archived task back to the default (`current`) task list.
+#[napi]
+pub async fn cloud_tasks_unarchive(config: CloudTasksConfig, task_id: String) -> Result<()> {
+    post_task_action_with_config(&config, &task_id, "unarchive").await
+}
+
 #[napi]
 pub async fn cloud_tasks_get_diff(config: CloudTasksConfig, task_id: String) -> Result<Option<String>> {
     let backend = create_backend(config).await.map_err(to_napi_error)?;
@@ -254,6 +377,525 @@ pub async fn cloud_tasks_apply(
     Ok(to_apply_outcome_napi(outcome))
 }
 
+/// Polls `task_id` until every best-of-N attempt has completed, failed or been
+/// cancelled and resolves with the task's final text. The first poll reports the current
+/// state; later events are only sent for what changed. Rejects with `timeout`,
+/// `unauthorized` when a poll gets a 401, or `cloud_request_failed` when it fails
+/// otherwise.
+#[napi]
+pub async fn cloud_tasks_watch(
+    config: CloudTasksConfig,
+    task_id: String,
+    opts: Option<WatchTaskOptionsNapi>,
+    callback: ThreadsafeFunction<TaskWatchEventNapi, ()>,
+) -> Result<TaskTextNapi> {
+    let backend = create_backend(config).await.map_err(to_napi_error)?;
+    watch_task(backend.as_ref(), task_id, opts.unwrap_or_default(), &callback).await
+}
+
+/// Long-lived cloud tasks handle. The backend and ChatGPT headers are built on
+/// first use and, with CLI-managed tokens, reused until the token expires or a
+/// request comes back 401; the tokens are then refreshed and both are rebuilt,
+/// retrying the failed request once (for `watch`, each poll). An explicit
+/// `bearerToken` is never refreshed, so its 401 reaches the caller. Requests
+/// this binding sends itself (task list pages, environments, task actions)
+/// share one connection pool; those sent through the `CloudBackend` use the
+/// backend's own. In mock mode, archiving and cancelling are remembered by
+/// this client for its own `list`, `getText` and `watch`.
+#[napi]
+pub struct CloudTasksClient {
+    config: CloudTasksConfig,
+    session: tokio::sync::Mutex<Option<Arc<CloudSession>>>,
+    http: reqwest::Client,
+    mock_state: Mutex<MockTaskState>,
+}
+
+#[napi]
+impl CloudTasksClient {
+    #[napi(constructor)]
+    pub fn new(config: CloudTasksConfig) -> Result<Self> {
+        Ok(Self {
+            config,
+            session: tokio::sync::Mutex::default(),
+            http: reqwest::Client::builder().build().map_err(to_napi_error)?,
+            mock_state: Mutex::default(),
+        })
+    }
+
+    #[napi]
+    pub async fn list(&self, environment_id: Option<String>) -> Result<Vec<TaskSummaryNapi>> {
+        let opts = ListTasksOptionsNapi {
+            environment_id,
+            ..Default::default()
+        };
+        Ok(self.list_page(Some(opts)).await?.tasks)
+    }
+
+    #[napi]
+    pub async fn list_page(&self, opts: Option<ListTasksOptionsNapi>) -> Result<TaskListPageNapi> {
+        let opts = opts.unwrap_or_default();
+        if self.config.mock.unwrap_or(false) {
+            let mock_state = self.mock_state.lock().unwrap().clone();
+            return list_mock_task_page(self.session().await?.backend.as_ref(), &mock_state, opts).await;
+        }
+        let filter = TaskFilter::new(&opts)?;
+        let query = task_list_query(opts);
+        let base_url = normalize_base_url(&self.config.base_url);
+        let (http, base_url, query) = (&self.http, base_url.as_str(), query.as_slice());
+        let body = self
+            .with_session(|session| async move { fetch_task_list(http, base_url, &session.headers, query).await })
+            .await?;
+        Ok(to_task_list_page(&body, &filter))
+    }
+
+    #[napi]
+    pub async fn list_environments(&self) -> Result<Vec<EnvironmentRowNapi>> {
+        let base_url = normalize_base_url(&self.config.base_url);
+        let (http, base_url) = (&self.http, base_url.as_str());
+        self.with_session(|session| async move { list_environments(http, base_url, &session.headers).await })
+            .await
+    }
+
+    #[napi]
+    pub async fn create(&self, opts: CreateTaskOptionsNapi) -> Result<String> {
+        let opts = &opts;
+        let created: CreatedTask = self
+            .with_session(|session| async move {
+                session
+                    .backend
+                    .create_task(
+                        &opts.environment_id,
+                        &opts.prompt,
+                        &opts.git_ref,
+                        opts.qa_mode.unwrap_or(false),
+                        opts.best_of_n.unwrap_or(1) as usize,
+                    )
+                    .await
+            })
+            .await?;
+        Ok(created.id.0)
+    }
+
+    #[napi]
+    pub async fn cancel(&self, task_id: String) -> Result<()> {
+        self.post_task_action(&task_id, "cancel").await
+    }
+
+    #[napi]
+    pub async fn archive(&self, task_id: String) -> Result<()> {
+        self.post_task_action(&task_id, "archive").await
+    }
+
+    #[napi]
+    pub async fn unarchive(&self, task_id: String) -> Result<()> {
+        self.post_task_action(&task_id, "unarchive").await
+    }
+
+    #[napi]
+    pub async fn get_diff(&self, task_id: String) -> Result<Option<String>> {
+        let task_id = &task_id;
+        self.with_session(|session| async move { session.backend.get_task_diff(TaskId(task_id.clone())).await })
+            .await
+    }
+
+    #[napi]
+    pub async fn get_messages(&self, task_id: String) -> Result<Vec<String>> {
+        let task_id = &task_id;
+        self.with_session(|session| async move { session.backend.get_task_messages(TaskId(task_id.clone())).await })
+            .await
+    }
+
+    #[napi]
+    pub async fn get_text(&self, task_id: String) -> Result<TaskTextNapi> {
+        let text = self.task_text(&task_id).await?;
+        Ok(to_task_text_napi(text))
+    }
+
+    #[napi]
+    pub async fn list_attempts(&self, task_id: String, turn_id: String) -> Result<Vec<TurnAttemptNapi>> {
+        let attempts = self.sibling_attempts(&task_id, &turn_id).await?;
+        Ok(attempts.into_iter().map(to_turn_attempt_napi).collect())
+    }
+
+    #[napi]
+    pub async fn apply(
+        &self,
+        task_id: String,
+        diff_override: Option<String>,
+        preflight: bool,
+    ) -> Result<ApplyOutcomeNapi> {
+        let (task_id, diff_override) = (&task_id, &diff_override);
+        let outcome = self
+            .with_session(|session| async move {
+                let (task_id, diff_override) = (TaskId(task_id.clone()), diff_override.clone());
+                if preflight {
+                    session.backend.apply_task_preflight(task_id, diff_override).await
+                } else {
+                    session.backend.apply_task(task_id, diff_override).await
+                }
+            })
+            .await?;
+        Ok(to_apply_outcome_napi(outcome))
+    }
+
+    #[napi]
+    pub async fn watch(
+        &self,
+        task_id: String,
+        opts: Option<WatchTaskOptionsNapi>,
+        callback: ThreadsafeFunction<TaskWatchEventNapi, ()>,
+    ) -> Result<TaskTextNapi> {
+        watch_task(self, task_id, opts.unwrap_or_default(), &callback).await
+    }
+}
+
+impl CloudTasksClient {
+    /// Returns the cached session, rebuilding it once its bearer token expired.
+    async fn session(&self) -> Result<Arc<CloudSession>> {
+        let mut session = self.session.lock().await;
+        if let Some(current) = session.as_ref().filter(|current| !current.is_expired()) {
+            return Ok(current.clone());
+        }
+        if session.is_some() {
+            refresh_cli_tokens(&self.config).await;
+        }
+        let fresh = Arc::new(CloudSession::new(&self.config).await?);
+        *session = Some(fresh.clone());
+        Ok(fresh)
+    }
+
+    /// Drops `stale` after a 401 so the next call rebuilds it from refreshed
+    /// tokens. A session already replaced by a concurrent call is left alone.
+    async fn invalidate(&self, stale: &Arc<CloudSession>) {
+        let mut session = self.session.lock().await;
+        if session.as_ref().is_some_and(|current| Arc::ptr_eq(current, stale)) {
+            refresh_cli_tokens(&self.config).await;
+            *session = None;
+        }
+    }
+
+    /// Runs `op` against the current session and retries it once on a fresh
+    /// session if the backend answered 401. An explicit `bearer_token` would
+    /// only be sent again, so its 401 is returned as is.
+    async fn with_session<T, E, F, Fut>(&self, op: F) -> Result<T>
+    where
+        E: std::fmt::Display,
+        F: Fn(Arc<CloudSession>) -> Fut,
+        Fut: std::future::Future<Output = std::result::Result<T, E>>,
+    {
+        let session = self.session().await?;
+        match op(session.clone()).await {
+            Err(err) if is_unauthorized(&err) && self.config.bearer_token.is_none() => {
+                self.invalidate(&session).await;
+                op(self.session().await?).await.map_err(to_napi_error)
+            }
+            result => result.map_err(to_napi_error),
+        }
+    }
+
+    async fn post_task_action(&self, task_id: &str, action: &str) -> Result<()> {
+        if self.config.mock.unwrap_or(false) {
+            self.mock_state.lock().unwrap().record(task_id, action);
+            return Ok(());
+        }
+        let base_url = normalize_base_url(&self.config.base_url);
+        let (http, base_url) = (&self.http, base_url.as_str());
+        self.with_session(|session| async move {
+            post_task_action(http, base_url, &session.headers, task_id, action).await
+        })
+        .await
+    }
+}
+
+/// Seconds before `exp` at which a bearer token is treated as expired.
+const TOKEN_EXPIRY_MARGIN_SECS: i64 = 60;
+
+/// Backend and ChatGPT headers built from one read of the credentials.
+struct CloudSession {
+    backend: Box<dyn CloudBackend>,
+    headers: HeaderMap,
+    /// `exp` of the CLI-managed bearer token, when it is a JWT.
+    expires_at: Option<i64>,
+}
+
+impl CloudSession {
+    async fn new(config: &CloudTasksConfig) -> Result<Self> {
+        let headers = if config.mock.unwrap_or(false) {
+            HeaderMap::new()
+        } else {
+            build_chatgpt_headers(config).await
+        };
+        // An explicit `bearer_token` cannot be refreshed, so rebuilding on
+        // expiry would only repeat the work; its 401 reaches the caller.
+        let expires_at = headers
+            .get(AUTHORIZATION)
+            .filter(|_| config.bearer_token.is_none())
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "))
+            .and_then(jwt_expiry);
+        let backend = create_backend(config.clone()).await.map_err(to_napi_error)?;
+        Ok(Self { backend, headers, expires_at })
+    }
+
+    fn is_expired(&self) -> bool {
+        self.expires_at
+            .is_some_and(|exp| exp - TOKEN_EXPIRY_MARGIN_SECS <= chrono::Utc::now().timestamp())
+    }
+}
+
+/// Refreshes the ChatGPT tokens in `auth.json` so the next session picks up a
+/// new access token. An explicit `bearer_token` cannot be refreshed.
+async fn refresh_cli_tokens(config: &CloudTasksConfig) {
+    if config.bearer_token.is_some() || config.mock.unwrap_or(false) {
+        return;
+    }
+    let codex_home_path = match &config.codex_home {
+        Some(home) => std::path::PathBuf::from(home),
+        None => match config::find_codex_home() {
+            Ok(path) => path,
+            Err(_) => return,
+        },
+    };
+    if let Err(e) = AuthManager::shared(codex_home_path, false).refresh_token().await {
+        if std::env::var("CODEX_DEBUG").ok().as_deref() == Some("1") {
+            eprintln!("[codex-napi] cloud_tasks: token refresh failed: {e}");
+        }
+    }
+}
+
+/// Both `HttpClient` and this module format failed responses as
+/// `... failed: {status}; ...`.
+fn is_unauthorized(err: &impl std::fmt::Display) -> bool {
+    err.to_string()
+        .contains(&reqwest::StatusCode::UNAUTHORIZED.to_string())
+}
+
+const WATCH_DEFAULT_INTERVAL_MS: u32 = 2_000;
+const WATCH_MAX_INTERVAL: Duration = Duration::from_secs(30);
+
+/// The reads a watch polls. `CloudTasksClient` routes each one through
+/// `with_session`, so a token that expires mid-watch is refreshed between polls.
+trait TaskPoller {
+    async fn task_text(&self, task_id: &str) -> Result<TaskText>;
+    async fn sibling_attempts(&self, task_id: &str, turn_id: &str) -> Result<Vec<TurnAttempt>>;
+    async fn task_diff(&self, task_id: &str) -> Result<Option<String>>;
+}
+
+impl TaskPoller for dyn CloudBackend + '_ {
+    async fn task_text(&self, task_id: &str) -> Result<TaskText> {
+        self.get_task_text(TaskId(task_id.to_string())).await.map_err(to_napi_error)
+    }
+
+    async fn sibling_attempts(&self, task_id: &str, turn_id: &str) -> Result<Vec<TurnAttempt>> {
+        self.list_sibling_attempts(TaskId(task_id.to_string()), turn_id.to_string())
+            .await
+            .map_err(to_napi_error)
+    }
+
+    async fn task_diff(&self, task_id: &str) -> Result<Option<String>> {
+        self.get_task_diff(TaskId(task_id.to_string())).await.map_err(to_napi_error)
+    }
+}
+
+impl TaskPoller for CloudTasksClient {
+    async fn task_text(&self, task_id: &str) -> Result<TaskText> {
+        let mut text = self
+            .with_session(|session| async move { session.backend.get_task_text(TaskId(task_id.to_string())).await })
+            .await?;
+        self.mock_state.lock().unwrap().apply_to_attempt(task_id, &mut text.attempt_status);
+        Ok(text)
+    }
+
+    async fn sibling_attempts(&self, task_id: &str, turn_id: &str) -> Result<Vec<TurnAttempt>> {
+        let mut attempts = self
+            .with_session(|session| async move {
+                session
+                    .backend
+                    .list_sibling_attempts(TaskId(task_id.to_string()), turn_id.to_string())
+                    .await
+            })
+            .await?;
+        let mock_state = self.mock_state.lock().unwrap();
+        for attempt in &mut attempts {
+            mock_state.apply_to_attempt(task_id, &mut attempt.status);
+        }
+        Ok(attempts)
+    }
+
+    async fn task_diff(&self, task_id: &str) -> Result<Option<String>> {
+        self.with_session(|session| async move { session.backend.get_task_diff(TaskId(task_id.to_string())).await })
+            .await
+    }
+}
+
+async fn watch_task<P: TaskPoller + ?Sized>(
+    poller: &P,
+    task_id: String,
+    opts: WatchTaskOptionsNapi,
+    callback: &ThreadsafeFunction<TaskWatchEventNapi, ()>,
+) -> Result<TaskTextNapi> {
+    let interval = Duration::from_millis(opts.interval_ms.unwrap_or(WATCH_DEFAULT_INTERVAL_MS).max(1).into());
+    let attempt_total = opts.attempt_total.unwrap_or(1).max(1) as usize;
+    let watch = poll_until_settled(poller, &task_id, interval, attempt_total, callback);
+    match opts.timeout_ms {
+        Some(ms) => tokio::time::timeout(Duration::from_millis(ms.into()), watch)
+            .await
+            .map_err(|_| native_error("timeout", format!("task {task_id} did not settle within {ms} ms")))?,
+        None => watch.await,
+    }
+}
+
+struct AttemptState {
+    turn_id: Option<String>,
+    placement: Option<u32>,
+    status: String,
+}
+
+async fn poll_until_settled<P: TaskPoller + ?Sized>(
+    poller: &P,
+    task_id: &str,
+    interval: Duration,
+    attempt_total: usize,
+    callback: &ThreadsafeFunction<TaskWatchEventNapi, ()>,
+) -> Result<TaskTextNapi> {
+    let event = |event_type: &str| TaskWatchEventNapi {
+        event_type: event_type.to_string(),
+        task_id: task_id.to_string(),
+        turn_id: None,
+        attempt_placement: None,
+        status: None,
+        summary: None,
+    };
+    let mut attempt_statuses: std::collections::HashMap<Option<String>, String> = std::collections::HashMap::new();
+    let mut task_status: Option<&str> = None;
+    let mut diff_stats: Option<(u32, u32, u32)> = None;
+    let mut lone_attempt_finished = false;
+    let mut delay = interval;
+
+    loop {
+        let text = poller.task_text(task_id).await.map_err(watch_poll_error)?;
+        let mut attempts = vec![AttemptState {
+            turn_id: text.turn_id.clone(),
+            placement: text.attempt_placement.map(|v| v as u32),
+            status: attempt_status_to_string(&text.attempt_status),
+        }];
+        if let Some(turn_id) = text.turn_id.as_deref().filter(|_| !text.sibling_turn_ids.is_empty()) {
+            let siblings = poller.sibling_attempts(task_id, turn_id).await.map_err(watch_poll_error)?;
+            attempts.extend(
+                siblings
+                    .into_iter()
+                    .filter(|a| text.turn_id.as_ref() != Some(&a.turn_id))
+                    .map(|a| AttemptState {
+                        turn_id: Some(a.turn_id),
+                        placement: a.attempt_placement.map(|v| v as u32),
+                        status: attempt_status_to_string(&a.status),
+                    }),
+            );
+        }
+
+        let mut changed = false;
+        for attempt in &attempts {
+            let previous = attempt_statuses.insert(attempt.turn_id.clone(), attempt.status.clone());
+            if previous.as_ref() != Some(&attempt.status) {
+                changed = true;
+                emit_watch_event(callback, TaskWatchEventNapi {
+                    turn_id: attempt.turn_id.clone(),
+                    attempt_placement: attempt.placement,
+                    status: Some(attempt.status.clone()),
+                    ..event("attempt")
+                })
+                .await;
+            }
+        }
+
+        // The task's own sibling list is authoritative. Until it shows up the
+        // caller's count stands in, for one more poll once the first attempt
+        // has finished. An `unknown` status is not a final one.
+        let expected = if !text.sibling_turn_ids.is_empty() {
+            text.sibling_turn_ids.len() + 1
+        } else if lone_attempt_finished {
+            1
+        } else {
+            attempt_total
+        };
+        lone_attempt_finished = text.sibling_turn_ids.is_empty() && is_final_attempt_status(&attempts[0].status);
+        let settled = attempts.len() >= expected && attempts.iter().all(|a| is_final_attempt_status(&a.status));
+        let status = if !settled {
+            "pending"
+        } else if attempts.iter().any(|a| a.status == "completed") {
+            "ready"
+        } else {
+            "error"
+        };
+        if task_status != Some(status) {
+            task_status = Some(status);
+            changed = true;
+            emit_watch_event(callback, TaskWatchEventNapi {
+                status: Some(status.to_string()),
+                ..event("status")
+            })
+            .await;
+        }
+
+        // Once settled the diff only needs one last read, on the poll that settled it.
+        if changed || !settled {
+            let diff = poller.task_diff(task_id).await.map_err(watch_poll_error)?;
+            let stats = diff.as_deref().map(count_diff_stats).unwrap_or_default();
+            if diff_stats != Some(stats) {
+                diff_stats = Some(stats);
+                changed = true;
+                emit_watch_event(callback, TaskWatchEventNapi {
+                    turn_id: text.turn_id.clone(),
+                    attempt_placement: text.attempt_placement.map(|v| v as u32),
+                    summary: Some(DiffSummaryNapi {
+                        files_changed: stats.0,
+                        lines_added: stats.1,
+                        lines_removed: stats.2,
+                    }),
+                    ..event("diff_summary")
+                })
+                .await;
+            }
+        }
+
+        if settled {
+            return Ok(to_task_text_napi(text));
+        }
+        delay = if changed { interval } else { (delay * 2).min(WATCH_MAX_INTERVAL) };
+        tokio::time::sleep(delay).await;
+    }
+}
+
+fn is_final_attempt_status(status: &str) -> bool {
+    matches!(status, "completed" | "failed" | "cancelled")
+}
+
+/// Gives a failed poll a stable code; the reason keeps the backend's message.
+fn watch_poll_error(err: napi::Error) -> napi::Error {
+    let code = if is_unauthorized(&err.reason) { "unauthorized" } else { "cloud_request_failed" };
+    native_error(code, err.reason)
+}
+
+/// A released callback only stops the events; the watch still resolves.
+async fn emit_watch_event(callback: &ThreadsafeFunction<TaskWatchEventNapi, ()>, event: TaskWatchEventNapi) {
+    let _ = callback.call_async(Ok(event)).await;
+}
+
+/// `(files_changed, lines_added, lines_removed)` of a unified diff.
+fn count_diff_stats(diff: &str) -> (u32, u32, u32) {
+    diff.lines().fold((0, 0, 0), |(files, added, removed), line| {
+        if line.starts_with("diff --git ") {
+            (files + 1, added, removed)
+        } else if line.starts_with('+') && !line.starts_with("+++") {
+            (files, added + 1, removed)
+        } else if line.starts_with('-') && !line.starts_with("---") {
+            (files, added, removed + 1)
+        } else {
+            (files, added, removed)
+        }
+    })
+}
+
 async fn create_backend(config: CloudTasksConfig) -> anyhow::Result<Box<dyn CloudBackend>> {
     if config.mock.unwrap_or(false) {
         return Ok(Box::<MockClient>::default());
@@ -449,6 +1091,239 @@ async fn get_envs(client: &reqwest::Client, url: &str, headers: &HeaderMap) -> a
     Ok(rows)
 }
 
+const DEFAULT_TASK_PAGE_LIMIT: u32 = 20;
+const TASK_STATUSES: &[&str] = &["pending", "ready", "applied", "error"];
+
+/// Query for one page of the raw task list.
+fn task_list_query(opts: ListTasksOptionsNapi) -> Vec<(&'static str, String)> {
+    let task_filter = if opts.archived == Some(true) { "archived" } else { "current" };
+    let mut query = vec![
+        ("limit", opts.limit.unwrap_or(DEFAULT_TASK_PAGE_LIMIT).max(1).to_string()),
+        ("task_filter", task_filter.to_string()),
+    ];
+    if let Some(env) = opts.environment_id {
+        query.push(("environment_id", env));
+    }
+    if let Some(cursor) = opts.cursor {
+        query.push(("cursor", cursor));
+    }
+    query
+}
+
+fn to_task_list_page(body: &JsonValue, filter: &TaskFilter) -> TaskListPageNapi {
+    let tasks = body
+        .get("items")
+        .and_then(JsonValue::as_array)
+        .map(|items| items.iter().filter_map(raw_to_task_summary_napi).collect::<Vec<_>>())
+        .unwrap_or_default();
+    // The list is newest first, so later pages only hold older tasks.
+    let reached_cutoff = tasks.iter().any(|t| filter.is_before_cutoff(t));
+    TaskListPageNapi {
+        tasks: tasks.into_iter().filter(|t| filter.matches(t)).collect(),
+        next_cursor: body
+            .get("cursor")
+            .and_then(JsonValue::as_str)
+            .filter(|c| !c.is_empty() && !reached_cutoff)
+            .map(|c| c.to_string()),
+    }
+}
+
+/// `MockClient` has no cursors, so its single page is filtered and truncated
+/// locally, after applying the mock-mode actions in `mock_state`.
+async fn list_mock_task_page(
+    backend: &dyn CloudBackend,
+    mock_state: &MockTaskState,
+    opts: ListTasksOptionsNapi,
+) -> Result<TaskListPageNapi> {
+    let filter = TaskFilter::new(&opts)?;
+    let tasks = backend
+        .list_tasks(opts.environment_id.as_deref())
+        .await
+        .map_err(to_napi_error)?;
+    Ok(TaskListPageNapi {
+        tasks: tasks
+            .into_iter()
+            .map(to_task_summary_napi)
+            .map(|t| mock_state.apply_to_summary(t))
+            .filter(|t| filter.matches(t))
+            .take(opts.limit.unwrap_or(DEFAULT_TASK_PAGE_LIMIT).max(1) as usize)
+            .collect(),
+        next_cursor: None,
+    })
+}
+
+struct TaskFilter {
+    statuses: Option<Vec<String>>,
+    archived: bool,
+    is_review: Option<bool>,
+    updated_since: Option<chrono::DateTime<chrono::FixedOffset>>,
+}
+
+impl TaskFilter {
+    fn new(opts: &ListTasksOptionsNapi) -> Result<Self> {
+        if let Some(status) = opts
+            .statuses
+            .iter()
+            .flatten()
+            .find(|s| !TASK_STATUSES.contains(&s.as_str()))
+        {
+            return Err(native_error("invalid_option", format!("unknown task status `{status}`")));
+        }
+        let updated_since = opts
+            .updated_since
+            .as_deref()
+            .map(|raw| {
+                chrono::DateTime::parse_from_rfc3339(raw).map_err(|_| {
+                    native_error("invalid_option", format!("invalid updated_since timestamp `{raw}`"))
+                })
+            })
+            .transpose()?;
+        Ok(Self {
+            statuses: opts.statuses.clone(),
+            archived: opts.archived.unwrap_or(false),
+            is_review: opts.is_review,
+            updated_since,
+        })
+    }
+
+    fn matches(&self, t: &TaskSummaryNapi) -> bool {
+        if self.statuses.as_ref().is_some_and(|s| !s.contains(&t.status)) {
+            return false;
+        }
+        // The backend already filtered by `task_filter`; only drop items that say otherwise.
+        if t.archived.is_some_and(|a| a != self.archived) {
+            return false;
+        }
+        if self.is_review.is_some_and(|r| r != t.is_review) {
+            return false;
+        }
+        !self.is_before_cutoff(t)
+    }
+
+    /// True when `t` was last updated before `updated_since`.
+    fn is_before_cutoff(&self, t: &TaskSummaryNapi) -> bool {
+        match (self.updated_since, chrono::DateTime::parse_from_rfc3339(&t.updated_at)) {
+            (Some(since), Ok(updated_at)) => updated_at < since,
+            _ => false,
+        }
+    }
+}
+
+/// GETs one page of the raw task list: `{ "items": [...], "cursor": ... }`.
+async fn fetch_task_list(
+    client: &reqwest::Client,
+    base_url: &str,
+    headers: &HeaderMap,
+    query: &[(&str, String)],
+) -> anyhow::Result<JsonValue> {
+    let url = if base_url.contains("/backend-api") {
+        format!("{}/wham/tasks/list", base_url)
+    } else {
+        format!("{}/api/codex/tasks/list", base_url)
+    };
+    let res = client.get(&url).headers(headers.clone()).query(query).send().await?;
+    let status = res.status();
+    let body = res.text().await.unwrap_or_default();
+    if !status.is_success() {
+        anyhow::bail!("GET {url} failed: {status}; body={body}");
+    }
+    Ok(serde_json::from_str(&body)?)
+}
+
+/// `MockClient` keeps no task state, so mock-mode actions send no request. A
+/// `CloudTasksClient` records them here and shows them in its own list and
+/// watch; the `cloud_tasks_*` functions keep no mock state.
+#[derive(Default, Clone)]
+struct MockTaskState {
+    archived: BTreeSet<String>,
+    cancelled: BTreeSet<String>,
+}
+
+impl MockTaskState {
+    fn record(&mut self, task_id: &str, action: &str) {
+        match action {
+            "archive" => {
+                self.archived.insert(task_id.to_string());
+            }
+            "unarchive" => {
+                self.archived.remove(task_id);
+            }
+            "cancel" => {
+                self.cancelled.insert(task_id.to_string());
+            }
+            _ => {}
+        }
+    }
+
+    /// A cancelled task that was still pending is listed as `error`.
+    fn apply_to_summary(&self, t: TaskSummaryNapi) -> TaskSummaryNapi {
+        let status = if t.status == "pending" && self.cancelled.contains(&t.id) {
+            "error".to_string()
+        } else {
+            t.status
+        };
+        TaskSummaryNapi {
+            archived: Some(self.archived.contains(&t.id)),
+            status,
+            ..t
+        }
+    }
+
+    /// Unfinished attempts of a cancelled task report `cancelled`.
+    fn apply_to_attempt(&self, task_id: &str, status: &mut AttemptStatus) {
+        if self.cancelled.contains(task_id)
+            && !matches!(status, AttemptStatus::Completed | AttemptStatus::Failed | AttemptStatus::Cancelled)
+        {
+            *status = AttemptStatus::Cancelled;
+        }
+    }
+}
+
+async fn post_task_action_with_config(config: &CloudTasksConfig, task_id: &str, action: &str) -> Result<()> {
+    // Nothing to remember it in; see `MockTaskState`.
+    if config.mock.unwrap_or(false) {
+        return Ok(());
+    }
+    let base_url = normalize_base_url(&config.base_url);
+    let headers = build_chatgpt_headers(config).await;
+    let client = reqwest::Client::builder().build().map_err(to_napi_error)?;
+    post_task_action(&client, &base_url, &headers, task_id, action)
+        .await
+        .map_err(to_napi_error)
+}
+
+/// POSTs to `tasks/{task_id}/{action}` (`cancel`, `archive` or `unarchive`).
+async fn post_task_action(
+    client: &reqwest::Client,
+    base_url: &str,
+    headers: &HeaderMap,
+    task_id: &str,
+    action: &str,
+) -> anyhow::Result<()> {
+    let url = task_action_url(base_url, task_id, action)?;
+    let res = client.post(url.clone()).headers(headers.clone()).send().await?;
+    let status = res.status();
+    if !status.is_success() {
+        let body = res.text().await.unwrap_or_default();
+        anyhow::bail!("POST {url} failed: {status}; body={body}");
+    }
+    Ok(())
+}
+
+/// Pushes each segment so a task id containing `/`, `?` or `#` is percent-encoded.
+fn task_action_url(base_url: &str, task_id: &str, action: &str) -> anyhow::Result<reqwest::Url> {
+    let prefix: &[&str] = if base_url.contains("/backend-api") { &["wham"] } else { &["api", "codex"] };
+    let mut url = reqwest::Url::parse(base_url)?;
+    url.path_segments_mut()
+        .map_err(|()| anyhow::anyhow!("base URL {base_url} cannot have a path"))?
+        .pop_if_empty()
+        .extend(prefix)
+        .push("tasks")
+        .push(task_id)
+        .push(action);
+    Ok(url)
+}
+
 fn get_git_origins() -> Vec<String> {
     // git config --get-regexp remote..*.url
     let out = std::process::Command::new("git").args(["config", "--get-regexp", "remote\\..*\\.url"]).output();
@@ -496,6 +1371,7 @@ fn to_napi_error<E: std::fmt::Display>(e: E) -> napi::Error {
     napi::Error::from_reason(e.to_string())
 }
 
+/// Mock mode only: `TaskSummary` carries none of the list item's extra fields.
 fn to_task_summary_napi(t: TaskSummary) -> TaskSummaryNapi {
     TaskSummaryNapi {
         id: t.id.0,
@@ -522,10 +1398,98 @@ fn to_task_summary_napi(t: TaskSummary) -> TaskSummaryNapi {
         intent: None,
         initial_intent: None,
         fix_task_id: None,
-        pull_requests: None, // TODO: Add PR parsing
+        pull_requests: None,
+    }
+}
+
+/// Builds a summary from a raw task list item, mirroring how `HttpClient`
+/// derives status, diff stats and attempt count. Task-level fields live on the
+/// item, environment and branch details on its `task_status_display`, and turn
+/// details on `task_status_display.latest_turn_status_display`.
+fn raw_to_task_summary_napi(raw: &JsonValue) -> Option<TaskSummaryNapi> {
+    let status_display = raw.get("task_status_display");
+    let latest_turn = status_display.and_then(|d| d.get("latest_turn_status_display"));
+    let str_field = |v: Option<&JsonValue>, key: &str| {
+        v.and_then(|v| v.get(key)).and_then(JsonValue::as_str).map(|s| s.to_string())
+    };
+    let bool_field = |key: &str| raw.get(key).and_then(JsonValue::as_bool);
+    let time_field = |key: &str| raw.get(key).and_then(JsonValue::as_f64).and_then(epoch_seconds_to_rfc3339);
+    let diff_stats = latest_turn.and_then(|l| l.get("diff_stats"));
+    let diff_stat = |key: &str| diff_stats.and_then(|d| d.get(key)).and_then(JsonValue::as_u64).unwrap_or(0) as u32;
+    let sibling_turn_ids = latest_turn
+        .and_then(|l| l.get("sibling_turn_ids"))
+        .and_then(JsonValue::as_array)
+        .map(|ids| ids.iter().filter_map(|id| id.as_str().map(|s| s.to_string())).collect::<Vec<_>>());
+
+    let turn_status = str_field(latest_turn, "turn_status");
+    let status = match turn_status.as_deref() {
+        Some("completed") => "ready",
+        Some("failed") | Some("cancelled") => "error",
+        Some(_) => "pending",
+        None => match status_display.and_then(|d| d.get("state")).and_then(JsonValue::as_str) {
+            Some(state @ ("pending" | "ready" | "applied" | "error")) => state,
+            _ => "pending",
+        },
+    };
+
+    Some(TaskSummaryNapi {
+        id: raw.get("id")?.as_str()?.to_string(),
+        title: raw.get("title").and_then(JsonValue::as_str).unwrap_or_default().to_string(),
+        status: status.to_string(),
+        updated_at: time_field("updated_at").unwrap_or_else(|| chrono::Utc::now().to_rfc3339()),
+        created_at: time_field("created_at"),
+        has_generated_title: bool_field("has_generated_title"),
+        environment_id: str_field(status_display, "environment_id"),
+        environment_label: str_field(status_display, "environment_label"),
+        summary: DiffSummaryNapi {
+            files_changed: diff_stat("files_modified"),
+            lines_added: diff_stat("lines_added"),
+            lines_removed: diff_stat("lines_removed"),
+        },
+        is_review: bool_field("is_review").unwrap_or(false),
+        attempt_total: sibling_turn_ids.as_ref().map(|ids| ids.len() as u32 + 1),
+        archived: bool_field("archived"),
+        has_unread_turn: bool_field("has_unread_turn"),
+        branch_name: str_field(status_display, "branch_name"),
+        turn_id: str_field(latest_turn, "turn_id"),
+        turn_status,
+        sibling_turn_ids,
+        intent: str_field(status_display, "intent"),
+        initial_intent: str_field(status_display, "initial_intent"),
+        fix_task_id: str_field(status_display, "fix_task_id"),
+        pull_requests: raw
+            .get("pull_requests")
+            .and_then(JsonValue::as_array)
+            .map(|prs| prs.iter().map(to_pull_request_napi).collect()),
+    })
+}
+
+/// Accepts either an `ExternalPullRequestResponse` (PR nested under
+/// `pull_request`) or a bare pull request object.
+fn to_pull_request_napi(v: &JsonValue) -> PullRequestNapi {
+    let pr = v.get("pull_request").unwrap_or(v);
+    let str_field = |key: &str| pr.get(key).and_then(JsonValue::as_str).map(|s| s.to_string());
+    PullRequestNapi {
+        number: pr.get("number").and_then(JsonValue::as_u64).map(|n| n as u32),
+        url: str_field("url"),
+        state: str_field("state"),
+        merged: pr.get("merged").and_then(JsonValue::as_bool),
+        title: str_field("title"),
+        body: str_field("body"),
+        base_branch: str_field("base"),
+        head_branch: str_field("head"),
+        base_sha: str_field("base_sha"),
+        head_sha: str_field("head_sha"),
+        merge_commit_sha: str_field("merge_commit_sha"),
     }
 }
 
+fn epoch_seconds_to_rfc3339(secs: f64) -> Option<String> {
+    let whole = secs.trunc() as i64;
+    let nanos = ((secs - secs.trunc()) * 1e9) as u32;
+    chrono::DateTime::from_timestamp(whole, nanos).map(|dt| dt.to_rfc3339())
+}
+
 fn to_apply_outcome_napi(o: ApplyOutcome) -> ApplyOutcomeNapi {
     ApplyOutcomeNapi {
         applied: o.applied,
@@ -585,3 +1549,277 @@ fn attempt_status_to_string(s: &AttemptStatus) -> String {
         AttemptStatus::Unknown => "unknown".to_string(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn task_item() -> JsonValue {
+        json!({
+            "id": "task_e_1",
+            "title": "Fix flaky test",
+            "has_generated_title": true,
+            "created_at": 1760000000.5,
+            "updated_at": 1760000600.0,
+            "archived": false,
+            "has_unread_turn": true,
+            "task_status_display": {
+                "environment_id": "env_1",
+                "environment_label": "acme/api",
+                "branch_name": "codex/fix-flaky-test",
+                "intent": "code",
+                "initial_intent": "ask",
+                "fix_task_id": "task_e_0",
+                "latest_turn_status_display": {
+                    "turn_id": "turn_1",
+                    "turn_status": "completed",
+                    "sibling_turn_ids": ["turn_2", "turn_3"],
+                    "diff_stats": { "files_modified": 2, "lines_added": 10, "lines_removed": 3 }
+                }
+            },
+            "pull_requests": [{
+                "pull_request": {
+                    "number": 42,
+                    "url": "https://github.com/acme/api/pull/42",
+                    "state": "closed",
+                    "merged": true,
+                    "title": "Fix flaky test",
+                    "base": "main",
+                    "head": "codex/fix-flaky-test",
+                    "merge_commit_sha": "abc123"
+                }
+            }]
+        })
+    }
+
+    fn task() -> TaskSummaryNapi {
+        raw_to_task_summary_napi(&task_item()).unwrap()
+    }
+
+    fn filter(opts: ListTasksOptionsNapi) -> TaskFilter {
+        TaskFilter::new(&opts).unwrap()
+    }
+
+    #[test]
+    fn raw_summary_reads_each_field() {
+        let t = task();
+        assert_eq!(t.id, "task_e_1");
+        assert_eq!(t.title, "Fix flaky test");
+        assert_eq!(t.status, "ready");
+        assert_eq!(t.updated_at, "2025-10-09T09:03:20+00:00");
+        assert_eq!(t.created_at.as_deref(), Some("2025-10-09T08:53:20.500+00:00"));
+        assert_eq!(t.has_generated_title, Some(true));
+        assert_eq!(t.environment_id.as_deref(), Some("env_1"));
+        assert_eq!(t.environment_label.as_deref(), Some("acme/api"));
+        assert_eq!(
+            (t.summary.files_changed, t.summary.lines_added, t.summary.lines_removed),
+            (2, 10, 3)
+        );
+        assert!(!t.is_review);
+        assert_eq!(t.attempt_total, Some(3));
+        assert_eq!(t.archived, Some(false));
+        assert_eq!(t.has_unread_turn, Some(true));
+        assert_eq!(t.branch_name.as_deref(), Some("codex/fix-flaky-test"));
+        assert_eq!(t.turn_id.as_deref(), Some("turn_1"));
+        assert_eq!(t.turn_status.as_deref(), Some("completed"));
+        assert_eq!(t.sibling_turn_ids, Some(vec!["turn_2".to_string(), "turn_3".to_string()]));
+        assert_eq!(t.intent.as_deref(), Some("code"));
+        assert_eq!(t.initial_intent.as_deref(), Some("ask"));
+        assert_eq!(t.fix_task_id.as_deref(), Some("task_e_0"));
+
+        let prs = t.pull_requests.unwrap();
+        assert_eq!(prs.len(), 1);
+        assert_eq!(prs[0].number, Some(42));
+        assert_eq!(prs[0].merged, Some(true));
+        assert_eq!(prs[0].head_branch.as_deref(), Some("codex/fix-flaky-test"));
+        assert_eq!(prs[0].merge_commit_sha.as_deref(), Some("abc123"));
+    }
+
+    #[test]
+    fn raw_summary_without_turn_uses_task_state() {
+        let t = raw_to_task_summary_napi(&json!({ "id": "t", "task_status_display": { "state": "applied" } })).unwrap();
+        assert_eq!(t.status, "applied");
+        assert_eq!(t.attempt_total, None);
+        assert_eq!(t.turn_status, None);
+        assert_eq!((t.summary.files_changed, t.summary.lines_added, t.summary.lines_removed), (0, 0, 0));
+
+        let t = raw_to_task_summary_napi(&json!({ "id": "t", "task_status_display": { "state": "bogus" } })).unwrap();
+        assert_eq!(t.status, "pending");
+        assert!(raw_to_task_summary_napi(&json!({ "title": "no id" })).is_none());
+    }
+
+    #[test]
+    fn raw_summary_maps_turn_status() {
+        for (turn_status, status) in [("failed", "error"), ("cancelled", "error"), ("in_progress", "pending")] {
+            let mut raw = task_item();
+            raw["task_status_display"]["latest_turn_status_display"]["turn_status"] = json!(turn_status);
+            assert_eq!(raw_to_task_summary_napi(&raw).unwrap().status, status);
+        }
+    }
+
+    #[test]
+    fn pull_request_accepts_bare_objects() {
+        let pr = to_pull_request_napi(&json!({ "number": 7, "state": "open", "head_sha": "def456" }));
+        assert_eq!(pr.number, Some(7));
+        assert_eq!(pr.state.as_deref(), Some("open"));
+        assert_eq!(pr.head_sha.as_deref(), Some("def456"));
+        assert_eq!(pr.merged, None);
+        assert_eq!(pr.url, None);
+    }
+
+    #[test]
+    fn counts_diff_stats() {
+        let diff = "\
+diff --git a/src/lib.rs b/src/lib.rs
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,2 +1,3 @@
+-old
++new
++added
+ context
+diff --git a/README.md b/README.md
+--- a/README.md
++++ b/README.md
+@@ -1 +1,2 @@
++more
+";
+        assert_eq!(count_diff_stats(diff), (2, 3, 1));
+        assert_eq!(count_diff_stats(""), (0, 0, 0));
+    }
+
+    #[test]
+    fn converts_epoch_seconds() {
+        assert_eq!(epoch_seconds_to_rfc3339(0.0).as_deref(), Some("1970-01-01T00:00:00+00:00"));
+        assert_eq!(epoch_seconds_to_rfc3339(1.5).as_deref(), Some("1970-01-01T00:00:01.500+00:00"));
+        assert_eq!(epoch_seconds_to_rfc3339(1e20), None);
+    }
+
+    #[test]
+    fn task_filter_rejects_invalid_options() {
+        let unknown_status = ListTasksOptionsNapi {
+            statuses: Some(vec!["done".to_string()]),
+            ..Default::default()
+        };
+        let bad_timestamp = ListTasksOptionsNapi {
+            updated_since: Some("yesterday".to_string()),
+            ..Default::default()
+        };
+        assert!(TaskFilter::new(&unknown_status).is_err());
+        assert!(TaskFilter::new(&bad_timestamp).is_err());
+    }
+
+    #[test]
+    fn task_filter_matches_each_option() {
+        let t = task();
+        assert!(filter(ListTasksOptionsNapi::default()).matches(&t));
+
+        let statuses = |s: &[&str]| ListTasksOptionsNapi {
+            statuses: Some(s.iter().map(|s| s.to_string()).collect()),
+            ..Default::default()
+        };
+        assert!(filter(statuses(&["ready", "applied"])).matches(&t));
+        assert!(!filter(statuses(&["pending"])).matches(&t));
+
+        let archived = ListTasksOptionsNapi {
+            archived: Some(true),
+            ..Default::default()
+        };
+        assert!(!filter(archived).matches(&t));
+        let unknown_archived = TaskSummaryNapi { archived: None, ..task() };
+        assert!(filter(ListTasksOptionsNapi { archived: Some(true), ..Default::default() }).matches(&unknown_archived));
+
+        let review = ListTasksOptionsNapi {
+            is_review: Some(true),
+            ..Default::default()
+        };
+        assert!(!filter(review).matches(&t));
+
+        let since = |ts: &str| ListTasksOptionsNapi {
+            updated_since: Some(ts.to_string()),
+            ..Default::default()
+        };
+        assert!(filter(since("2025-10-09T00:00:00Z")).matches(&t));
+        assert!(filter(since("2025-10-09T09:03:20Z")).matches(&t));
+        assert!(!filter(since("2025-10-10T00:00:00Z")).matches(&t));
+    }
+
+    #[test]
+    fn task_action_url_encodes_task_id() {
+        let url = task_action_url("https://chatgpt.com/backend-api", "task_e_1", "archive").unwrap();
+        assert_eq!(url.as_str(), "https://chatgpt.com/backend-api/wham/tasks/task_e_1/archive");
+
+        let url = task_action_url("http://localhost:8080", "a/b?c#d", "cancel").unwrap();
+        assert_eq!(url.as_str(), "http://localhost:8080/api/codex/tasks/a%2Fb%3Fc%23d/cancel");
+    }
+
+    #[test]
+    fn mock_task_state_records_actions() {
+        let mut state = MockTaskState::default();
+        state.record("task_e_1", "archive");
+        state.record("task_e_2", "archive");
+        state.record("task_e_2", "cancel");
+        state.record("task_e_1", "unarchive");
+        assert_eq!(state.archived, BTreeSet::from(["task_e_2".to_string()]));
+
+        let mut item = task_item();
+        item["task_status_display"]["latest_turn_status_display"]["turn_status"] = json!("in_progress");
+        let pending = raw_to_task_summary_napi(&item).unwrap();
+        let t = state.apply_to_summary(pending);
+        assert_eq!((t.status.as_str(), t.archived), ("pending", Some(false)));
+
+        item["id"] = json!("task_e_2");
+        let cancelled = raw_to_task_summary_napi(&item).unwrap();
+        let t = state.apply_to_summary(cancelled);
+        assert_eq!((t.status.as_str(), t.archived), ("error", Some(true)));
+
+        let mut status = AttemptStatus::InProgress;
+        state.apply_to_attempt("task_e_2", &mut status);
+        assert!(matches!(status, AttemptStatus::Cancelled));
+        let mut status = AttemptStatus::Completed;
+        state.apply_to_attempt("task_e_2", &mut status);
+        assert!(matches!(status, AttemptStatus::Completed));
+    }
+
+    #[test]
+    fn task_list_page_filters_items_and_keeps_cursor() {
+        let mut pending = task_item();
+        pending["id"] = json!("task_e_2");
+        pending["task_status_display"]["latest_turn_status_display"]["turn_status"] = json!("in_progress");
+        let body = json!({ "items": [task_item(), pending, { "title": "no id" }], "cursor": "next" });
+
+        let page = to_task_list_page(&body, &filter(ListTasksOptionsNapi {
+            statuses: Some(vec!["pending".to_string()]),
+            ..Default::default()
+        }));
+        assert_eq!(page.tasks.iter().map(|t| t.id.as_str()).collect::<Vec<_>>(), ["task_e_2"]);
+        assert_eq!(page.next_cursor.as_deref(), Some("next"));
+
+        let last = to_task_list_page(&json!({ "items": [], "cursor": "" }), &filter(ListTasksOptionsNapi::default()));
+        assert!(last.tasks.is_empty());
+        assert_eq!(last.next_cursor, None);
+    }
+
+    #[test]
+    fn task_list_page_stops_at_updated_since() {
+        let mut newer = task_item();
+        newer["id"] = json!("task_e_2");
+        newer["updated_at"] = json!(1760000700.0);
+        let body = json!({ "items": [newer, task_item()], "cursor": "next" });
+
+        let page = to_task_list_page(&body, &filter(ListTasksOptionsNapi {
+            updated_since: Some("2025-10-09T09:04:00Z".to_string()),
+            ..Default::default()
+        }));
+        assert_eq!(page.tasks.iter().map(|t| t.id.as_str()).collect::<Vec<_>>(), ["task_e_2"]);
+        assert_eq!(page.next_cursor, None);
+
+        let page = to_task_list_page(&body, &filter(ListTasksOptionsNapi {
+            updated_since: Some("2025-10-09T09:03:20Z".to_string()),
+            ..Default::default()
+        }));
+        assert_eq!(page.tasks.len(), 2);
+        assert_eq!(page.next_cursor.as_deref(), Some("next"));
+    }
+}
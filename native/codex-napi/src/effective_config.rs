@@ -0,0 +1,125 @@
+use codex_core::config::Config;
+use napi_derive::napi;
+use serde_json::Value as JsonValue;
+
+use crate::error::native_error;
+
+/// Keys whose values may hold credentials. Maps keep their keys so callers can
+/// still see which headers or variables are configured.
+const REDACTED_KEYS: &[&str] = &["env", "http_headers", "bearer_token", "experimental_bearer_token"];
+const REDACTED: &str = "[redacted]";
+
+#[napi(object)]
+pub struct EffectiveConfig {
+    pub model: String,
+    pub model_provider_id: String,
+    pub model_provider: JsonValue,
+    pub model_reasoning_effort: Option<String>,
+    pub approval_policy: String,
+    pub sandbox_policy: JsonValue,
+    pub cwd: String,
+    pub codex_home: String,
+    pub mcp_servers: JsonValue,
+    pub active_profile: Option<String>,
+}
+
+pub(crate) fn effective_config(config: &Config) -> napi::Result<EffectiveConfig> {
+    Ok(EffectiveConfig {
+        model: config.model.clone(),
+        model_provider_id: config.model_provider_id.clone(),
+        model_provider: redacted(to_json(&config.model_provider)?),
+        model_reasoning_effort: config
+            .model_reasoning_effort
+            .as_ref()
+            .map(to_json)
+            .transpose()?
+            .and_then(|value| value.as_str().map(str::to_string)),
+        approval_policy: to_json(&config.approval_policy)?
+            .as_str()
+            .unwrap_or_default()
+            .to_string(),
+        sandbox_policy: to_json(&config.sandbox_policy)?,
+        cwd: config.cwd.display().to_string(),
+        codex_home: config.codex_home.display().to_string(),
+        mcp_servers: redacted(to_json(&config.mcp_servers)?),
+        active_profile: config.active_profile.clone(),
+    })
+}
+
+fn to_json<T: serde::Serialize>(value: &T) -> napi::Result<JsonValue> {
+    serde_json::to_value(value).map_err(|err| native_error("serialization", err.to_string()))
+}
+
+fn redacted(mut value: JsonValue) -> JsonValue {
+    redact_in_place(&mut value);
+    value
+}
+
+fn redact_in_place(value: &mut JsonValue) {
+    match value {
+        JsonValue::Object(map) => {
+            for (key, entry) in map.iter_mut() {
+                if REDACTED_KEYS.contains(&key.as_str()) {
+                    redact_leaves(entry);
+                } else {
+                    redact_in_place(entry);
+                }
+            }
+        }
+        JsonValue::Array(items) => items.iter_mut().for_each(redact_in_place),
+        _ => {}
+    }
+}
+
+fn redact_leaves(value: &mut JsonValue) {
+    match value {
+        JsonValue::Object(map) => map.values_mut().for_each(redact_leaves),
+        JsonValue::Array(items) => items.iter_mut().for_each(redact_leaves),
+        JsonValue::Null => {}
+        _ => *value = JsonValue::String(REDACTED.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn redacts_credentials_but_keeps_keys() {
+        let value = redacted(json!({
+            "docs": {
+                "command": "docs-server",
+                "args": ["--port", "8080"],
+                "env": { "API_TOKEN": "secret", "DEBUG": null },
+            },
+            "remote": {
+                "url": "https://example.com/mcp",
+                "bearer_token": "secret",
+                "http_headers": { "X-Key": ["a", "b"] },
+            },
+        }));
+
+        assert_eq!(
+            value,
+            json!({
+                "docs": {
+                    "command": "docs-server",
+                    "args": ["--port", "8080"],
+                    "env": { "API_TOKEN": REDACTED, "DEBUG": null },
+                },
+                "remote": {
+                    "url": "https://example.com/mcp",
+                    "bearer_token": REDACTED,
+                    "http_headers": { "X-Key": [REDACTED, REDACTED] },
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn redacts_inside_arrays() {
+        let value = redacted(json!([{ "experimental_bearer_token": "secret", "name": "openai" }]));
+        assert_eq!(value, json!([{ "experimental_bearer_token": REDACTED, "name": "openai" }]));
+    }
+}
@@ -0,0 +1,113 @@
+use codex_core::error::{CodexErr, SandboxErr};
+use serde::Serialize;
+
+/// Stable code and structured fields of a native error. napi-rs rejects async
+/// calls with a plain `Error` whose `code` is a napi status, so these travel as
+/// JSON in `cause.message`; the TS loader (`normalizeNativeError`) moves them
+/// onto the error itself and drops the `cause`. `message` stays plain text.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct NativeErrorPayload {
+    code: &'static str,
+    #[serde(skip)]
+    message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    status_code: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    retry_after_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    details: Option<serde_json::Value>,
+}
+
+pub(crate) fn native_error(code: &'static str, message: impl Into<String>) -> napi::Error {
+    build(NativeErrorPayload {
+        code,
+        message: message.into(),
+        status_code: None,
+        retry_after_ms: None,
+        details: None,
+    })
+}
+
+pub(crate) fn native_error_with_details(
+    code: &'static str,
+    message: impl Into<String>,
+    details: serde_json::Value,
+) -> napi::Error {
+    build(NativeErrorPayload {
+        code,
+        message: message.into(),
+        status_code: None,
+        retry_after_ms: None,
+        details: Some(details),
+    })
+}
+
+/// The conversation channel reports closure as a dead agent loop.
+pub(crate) fn is_stream_closed(err: &CodexErr) -> bool {
+    matches!(err, CodexErr::InternalAgentDied)
+}
+
+pub(crate) fn codex_error(err: CodexErr) -> napi::Error {
+    let message = err.to_string();
+    let mut status_code = None;
+    let mut retry_after_ms = None;
+
+    let code = match &err {
+        CodexErr::InternalAgentDied => "stream_closed",
+        CodexErr::Stream(_, retry_after) => {
+            retry_after_ms = retry_after.map(|d| d.as_millis() as u64);
+            "stream_error"
+        }
+        CodexErr::Timeout => "timeout",
+        CodexErr::Interrupted => "interrupted",
+        CodexErr::UsageLimitReached(limit) => {
+            retry_after_ms = limit.resets_in_seconds.map(|secs| secs * 1000);
+            "usage_limit_reached"
+        }
+        CodexErr::UsageNotIncluded => "usage_not_included",
+        CodexErr::UnexpectedStatus(response) => {
+            let status = response.status.as_u16();
+            status_code = Some(status);
+            if status == 401 || status == 403 {
+                "unauthorized"
+            } else {
+                "unexpected_status"
+            }
+        }
+        CodexErr::RetryLimit(_) => "retry_limit",
+        CodexErr::ConversationNotFound(_) => "conversation_not_found",
+        CodexErr::Sandbox(SandboxErr::Denied { .. }) => "sandbox_denied",
+        CodexErr::Sandbox(SandboxErr::Timeout { .. }) => "sandbox_timeout",
+        CodexErr::Sandbox(_) => "sandbox_error",
+        CodexErr::Io(_) => "io",
+        _ => "internal",
+    };
+
+    build(NativeErrorPayload {
+        code,
+        message,
+        status_code,
+        retry_after_ms,
+        details: None,
+    })
+}
+
+/// Errors from loading `config.toml`; malformed TOML and invalid values
+/// surface as `InvalidData`/`InvalidInput`.
+pub(crate) fn config_error(err: std::io::Error) -> napi::Error {
+    let code = match err.kind() {
+        std::io::ErrorKind::InvalidData | std::io::ErrorKind::InvalidInput => "config_parse",
+        _ => "config_load",
+    };
+    native_error(code, err.to_string())
+}
+
+fn build(payload: NativeErrorPayload) -> napi::Error {
+    let fields = serde_json::to_string(&payload);
+    let mut error = napi::Error::from_reason(payload.message);
+    if let Ok(fields) = fields {
+        error.set_cause(napi::Error::from_reason(fields));
+    }
+    error
+}
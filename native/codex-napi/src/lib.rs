@@ -1,20 +1,249 @@
-use std::collections::VecDeque;
-use std::sync::{Arc, Mutex};
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::Duration;
 
-use codex_core::config::{self, Config, ConfigOverrides};
-use codex_core::{CodexConversation, ConversationManager};
-use codex_core::protocol::{Event, EventMsg, Submission};
+use codex_core::config::{self, Config, ConfigOverrides, ConfigToml};
+use codex_core::{CodexConversation, ConversationManager, NewConversation};
+use codex_core::protocol::{Event, EventMsg, InputItem, Op, ReviewDecision, Submission};
+use codex_core::default_client::{originator, set_default_originator, SetOriginatorError};
 use codex_core::AuthManager;
 use codex_protocol::ConversationId;
-use codex_protocol::protocol::SessionSource;
+use codex_protocol::config_types::SandboxMode;
+use codex_protocol::protocol::{AskForApproval, SessionSource};
+use napi::bindgen_prelude::{FromNapiValue, Object, Promise, ToNapiValue, TypeName};
+use napi::threadsafe_function::ThreadsafeFunction;
+use napi::{sys, Env, JsValue, ValueType};
 use napi_derive::napi;
+use tokio::task::JoinHandle;
+pub mod auth;
 pub mod cloud_tasks;
+pub mod effective_config;
+mod error;
+pub mod login;
+pub mod rollout;
+
+use auth::{auth_mode_name, auth_status, AuthNotifier, AuthStatus};
+use effective_config::{effective_config, EffectiveConfig};
+use login::{start_chatgpt_login, ChatGptLogin, ChatGptLoginOptions};
+use rollout::{list_sessions, ListSessionsOptions, SessionsPage};
+
+use error::{codex_error, config_error, is_stream_closed, native_error, native_error_with_details};
+
+const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(10);
+const DEFAULT_ORIGINATOR: &str = "codex_cli_rs";
 
 struct SessionInner {
     conversation_id: ConversationId,
     conversation: Arc<CodexConversation>,
     manager: Arc<ConversationManager>,
+    config: Config,
+    rollout_path: PathBuf,
     pending: Mutex<VecDeque<Event>>,
+    subscription: Mutex<Option<JoinHandle<()>>>,
+    registry: Weak<SessionRegistry>,
+}
+
+/// Live sessions created through one `NativeCodex`, keyed by conversation id,
+/// so JS can reattach after dropping its handle.
+#[derive(Default)]
+struct SessionRegistry {
+    sessions: Mutex<HashMap<String, Arc<SessionInner>>>,
+}
+
+impl SessionInner {
+    async fn next_event(&self) -> napi::Result<Option<Event>> {
+        if let Some(event) = self.pending.lock().unwrap().pop_front() {
+            return Ok(Some(event));
+        }
+
+        self.recv_event().await
+    }
+
+    /// Collects up to `max` events: everything already buffered plus whatever
+    /// the conversation yields without waiting. When nothing is available yet,
+    /// waits for the first event for at most `timeout` (forever if `None`).
+    /// Returns `None` once the stream is closed and nothing was collected.
+    async fn next_events(
+        &self,
+        max: usize,
+        timeout: Option<Duration>,
+    ) -> napi::Result<Option<Vec<Event>>> {
+        let mut events: Vec<Event> = {
+            let mut pending = self.pending.lock().unwrap();
+            let take = pending.len().min(max);
+            pending.drain(..take).collect()
+        };
+
+        while events.len() < max {
+            let wait = if events.is_empty() { timeout } else { Some(Duration::ZERO) };
+            let received = match wait {
+                Some(wait) => match tokio::time::timeout(wait, self.recv_event()).await {
+                    Ok(received) => received,
+                    Err(_elapsed) => break,
+                },
+                None => self.recv_event().await,
+            };
+
+            match received {
+                Ok(Some(event)) => events.push(event),
+                Ok(None) if events.is_empty() => return Ok(None),
+                Err(err) if events.is_empty() => return Err(err),
+                // Hand back what we already have; the next call surfaces the closure or error.
+                Ok(None) | Err(_) => break,
+            }
+        }
+
+        Ok(Some(events))
+    }
+
+    async fn shutdown_and_wait(&self) -> napi::Result<()> {
+        self.submit_op(Op::Shutdown).await?;
+        loop {
+            match self.recv_event().await? {
+                Some(Event {
+                    msg: EventMsg::ShutdownComplete,
+                    ..
+                })
+                | None => return Ok(()),
+                Some(event) => self.pending.lock().unwrap().push_back(event),
+            }
+        }
+    }
+
+    async fn submit_op(&self, op: Op) -> napi::Result<String> {
+        self.conversation.submit(op).await.map_err(codex_error)
+    }
+
+    async fn recv_event(&self) -> napi::Result<Option<Event>> {
+        match self.conversation.next_event().await {
+            Ok(event) => {
+                if matches!(event.msg, EventMsg::ShutdownComplete) {
+                    self.deregister().await;
+                }
+                Ok(Some(event))
+            }
+            Err(err) if is_stream_closed(&err) => {
+                self.deregister().await;
+                Ok(None)
+            }
+            Err(err) => Err(codex_error(err)),
+        }
+    }
+
+    /// Forgets a conversation that has ended, so it drops out of
+    /// `list_conversations` and the manager releases it. Buffered events stay
+    /// readable through existing handles.
+    async fn deregister(&self) {
+        if let Some(registry) = self.registry.upgrade() {
+            registry
+                .sessions
+                .lock()
+                .unwrap()
+                .remove(&self.conversation_id.to_string());
+        }
+        self.manager.remove_conversation(&self.conversation_id).await;
+    }
+}
+
+/// An `Event` as `{ id, type, msg }`, where `type` is the `EventMsg` tag (e.g.
+/// `agent_message_delta`). `msg` is written straight into JS values by napi's
+/// serde serializer, with no JSON string or `serde_json::Value` in between.
+pub struct CodexEventObject(Event);
+
+impl TypeName for CodexEventObject {
+    fn type_name() -> &'static str {
+        "CodexEventObject"
+    }
+
+    fn value_type() -> ValueType {
+        ValueType::Object
+    }
+}
+
+impl ToNapiValue for CodexEventObject {
+    unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> napi::Result<sys::napi_value> {
+        let env = Env::from_raw(env);
+        let msg = env.to_js_value(&val.0.msg)?.coerce_to_object()?;
+        let event_type: Option<String> = msg.get("type")?;
+        let mut object = Object::new(&env)?;
+        object.set("id", val.0.id)?;
+        object.set("type", event_type)?;
+        object.set("msg", msg)?;
+        Ok(object.raw())
+    }
+}
+
+/// What a `subscribe` callback returned. A Promise is awaited before the next
+/// event is delivered; any other value is ignored.
+pub enum CallbackReturn {
+    Promise(Promise<Ignored>),
+    Value,
+}
+
+impl FromNapiValue for CallbackReturn {
+    unsafe fn from_napi_value(env: sys::napi_env, value: sys::napi_value) -> napi::Result<Self> {
+        let mut is_promise = false;
+        napi::check_status!(sys::napi_is_promise(env, value, &mut is_promise))?;
+        if is_promise {
+            Ok(Self::Promise(Promise::from_napi_value(env, value)?))
+        } else {
+            Ok(Self::Value)
+        }
+    }
+}
+
+/// Whatever a callback's Promise resolved to.
+pub struct Ignored;
+
+impl FromNapiValue for Ignored {
+    unsafe fn from_napi_value(_env: sys::napi_env, _value: sys::napi_value) -> napi::Result<Self> {
+        Ok(Self)
+    }
+}
+
+/// Feeds events to a `subscribe` callback until the stream closes, the
+/// callback is released, or it throws or rejects.
+async fn deliver_events(
+    inner: &SessionInner,
+    callback: &ThreadsafeFunction<Option<String>, CallbackReturn>,
+) -> napi::Result<()> {
+    loop {
+        let value = match inner.next_event().await {
+            Ok(Some(event)) => serialize_event(event).map(Some),
+            Ok(None) => Ok(None),
+            Err(err) => Err(err),
+        };
+        let done = !matches!(value, Ok(Some(_)));
+        match callback.call_async(value).await {
+            Ok(CallbackReturn::Promise(promise)) => {
+                promise.await?;
+            }
+            Ok(CallbackReturn::Value) => {}
+            // The JS side released the callback.
+            Err(err) if err.status == napi::Status::Closing => return Ok(()),
+            Err(err) => return Err(err),
+        }
+        if done {
+            return Ok(());
+        }
+    }
+}
+
+#[napi(object)]
+pub struct UserInputItem {
+    /// One of `text`, `image` or `local_image`.
+    #[napi(js_name = "type")]
+    pub item_type: String,
+    pub text: Option<String>,
+    pub image_url: Option<String>,
+    pub path: Option<String>,
+}
+
+#[napi(object)]
+pub struct CloseOptions {
+    pub graceful: Option<bool>,
+    pub timeout_ms: Option<u32>,
 }
 
 #[napi(object)]
@@ -24,13 +253,36 @@ pub struct ConfigOverrideEntry {
 }
 
 #[napi(object)]
+#[derive(Default)]
 pub struct CreateConversationOptions {
     pub overrides: Option<Vec<ConfigOverrideEntry>>,
+    /// Reject overrides with unknown keys or values that do not fit the config
+    /// schema instead of passing them through; the error lists every bad entry.
+    pub strict_overrides: Option<bool>,
+    pub model: Option<String>,
+    pub cwd: Option<String>,
+    /// One of `untrusted`, `on-failure`, `on-request` or `never`.
+    pub approval_policy: Option<String>,
+    /// One of `read-only`, `workspace-write` or `danger-full-access`.
+    pub sandbox_mode: Option<String>,
+    pub model_provider: Option<String>,
+    pub config_profile: Option<String>,
+    pub base_instructions: Option<String>,
+    pub include_plan_tool: Option<bool>,
+    pub include_apply_patch_tool: Option<bool>,
+    pub include_view_image_tool: Option<bool>,
+    pub show_raw_agent_reasoning: Option<bool>,
 }
 
 #[napi(object)]
+#[derive(Default)]
 pub struct NativeCodexOptions {
     pub codex_home: Option<String>,
+    /// Recorded in rollouts; one of `cli`, `vscode`, `exec`, `mcp` (default) or `unknown`.
+    pub session_source: Option<String>,
+    /// Originator reported to the server. codex-core keeps a single originator per
+    /// process, so a value that differs from an earlier instance's is rejected.
+    pub originator: Option<String>,
 }
 
 #[napi]
@@ -47,39 +299,190 @@ impl CodexSession {
 
     #[napi]
     pub async fn next_event(&self) -> napi::Result<Option<String>> {
-        if let Some(event) = self.inner.pending.lock().unwrap().pop_front() {
-            return serialize_event(event).map(Some);
+        match self.inner.next_event().await? {
+            Some(event) => serialize_event(event).map(Some),
+            None => Ok(None),
         }
+    }
 
-        match self.inner.conversation.next_event().await {
-            Ok(event) => serialize_event(event).map(Some),
-            Err(err) => {
-                if err.to_string().contains("StreamClosed") {
-                    Ok(None)
-                } else {
-                    Err(napi::Error::from_reason(err.to_string()))
-                }
+    /// Drains up to `max` available events in a single call. If none are ready,
+    /// waits up to `timeout_ms` for the first one (indefinitely when omitted).
+    /// Resolves to `null` once the event stream is closed.
+    #[napi]
+    pub async fn next_events(
+        &self,
+        max: u32,
+        timeout_ms: Option<u32>,
+    ) -> napi::Result<Option<Vec<String>>> {
+        let timeout = timeout_ms.map(|ms| Duration::from_millis(ms as u64));
+        match self.inner.next_events(max as usize, timeout).await? {
+            Some(events) => events
+                .into_iter()
+                .map(serialize_event)
+                .collect::<napi::Result<Vec<_>>>()
+                .map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Like `next_event`, but hands the event over as a JS object instead of a
+    /// JSON string, so callers do not need `JSON.parse`.
+    #[napi]
+    pub async fn next_event_object(&self) -> napi::Result<Option<CodexEventObject>> {
+        Ok(self.inner.next_event().await?.map(CodexEventObject))
+    }
+
+    /// Pushes every event to `callback` as `(err, eventJson)` from a background
+    /// task instead of requiring a JS poll loop. If `callback` returns a Promise,
+    /// it is awaited before the next event is read, so an async callback applies
+    /// backpressure. `eventJson` is `null` when the stream closes. Resolves when
+    /// the stream closes or `unsubscribe` is called; if `callback` throws or its
+    /// Promise rejects, the subscription stops and this rejects with that error.
+    /// Do not mix with `next_event` polling on the same session.
+    #[napi(ts_args_type = "callback: (err: Error | null, eventJson: string | null) => void | Promise<void>")]
+    pub async fn subscribe(
+        &self,
+        callback: ThreadsafeFunction<Option<String>, CallbackReturn>,
+    ) -> napi::Result<()> {
+        let (done_tx, done_rx) = tokio::sync::oneshot::channel();
+        {
+            let mut subscription = self.inner.subscription.lock().unwrap();
+            if subscription.as_ref().is_some_and(|handle| !handle.is_finished()) {
+                return Err(native_error(
+                    "already_subscribed",
+                    "session already has an active subscription",
+                ));
             }
+
+            let inner = self.inner.clone();
+            *subscription = Some(tokio::spawn(async move {
+                let _ = done_tx.send(deliver_events(&inner, &callback).await);
+            }));
+        }
+        // `unsubscribe` aborts the task, which drops the sender.
+        done_rx.await.unwrap_or(Ok(()))
+    }
+
+    /// Stops a subscription started with `subscribe`; a no-op if there is none.
+    #[napi]
+    pub fn unsubscribe(&self) {
+        if let Some(handle) = self.inner.subscription.lock().unwrap().take() {
+            handle.abort();
         }
     }
 
+    /// Submits a full `Submission` JSON and resolves to its id.
     #[napi]
-    pub async fn submit(&self, submission_json: String) -> napi::Result<()> {
+    pub async fn submit(&self, submission_json: String) -> napi::Result<String> {
         let submission: Submission = serde_json::from_str(&submission_json)
-            .map_err(|err| napi::Error::from_reason(err.to_string()))?;
+            .map_err(|err| native_error("invalid_submission", err.to_string()))?;
+        let id = submission.id.clone();
         self.inner
             .conversation
             .submit_with_id(submission)
             .await
-            .map_err(|err| napi::Error::from_reason(err.to_string()))
+            .map_err(codex_error)?;
+        Ok(id)
     }
 
+    /// Submits a bare `Op` JSON; the id is generated natively and returned.
     #[napi]
-    pub async fn close(&self) -> napi::Result<()> {
-        self.inner
-            .manager
-            .remove_conversation(&self.inner.conversation_id)
-            .await;
+    pub async fn submit_op(&self, op_json: String) -> napi::Result<String> {
+        let op: Op = serde_json::from_str(&op_json)
+            .map_err(|err| native_error("invalid_submission", err.to_string()))?;
+        self.inner.submit_op(op).await
+    }
+
+    /// Sends a user turn built from `items`. Resolves to the submission id.
+    #[napi]
+    pub async fn submit_user_input(&self, items: Vec<UserInputItem>) -> napi::Result<String> {
+        let items = items
+            .into_iter()
+            .map(to_input_item)
+            .collect::<napi::Result<Vec<_>>>()?;
+        self.inner.submit_op(Op::UserInput { items }).await
+    }
+
+    #[napi]
+    pub async fn interrupt(&self) -> napi::Result<String> {
+        self.inner.submit_op(Op::Interrupt).await
+    }
+
+    /// `decision` is one of `approved`, `approved_for_session`, `denied` or `abort`.
+    #[napi]
+    pub async fn approve_exec(&self, id: String, decision: String) -> napi::Result<String> {
+        let decision = parse_review_decision(&decision)?;
+        self.inner.submit_op(Op::ExecApproval { id, decision }).await
+    }
+
+    /// `decision` is one of `approved`, `approved_for_session`, `denied` or `abort`.
+    #[napi]
+    pub async fn approve_patch(&self, id: String, decision: String) -> napi::Result<String> {
+        let decision = parse_review_decision(&decision)?;
+        self.inner.submit_op(Op::PatchApproval { id, decision }).await
+    }
+
+    #[napi]
+    pub async fn compact(&self) -> napi::Result<String> {
+        self.inner.submit_op(Op::Compact).await
+    }
+
+    #[napi]
+    pub async fn shutdown(&self) -> napi::Result<String> {
+        self.inner.submit_op(Op::Shutdown).await
+    }
+
+    /// The config this session was started with, secrets redacted.
+    #[napi]
+    pub fn config(&self) -> napi::Result<EffectiveConfig> {
+        effective_config(&self.inner.config)
+    }
+
+    #[napi(getter)]
+    pub fn rollout_path(&self) -> String {
+        self.inner.rollout_path.display().to_string()
+    }
+
+    /// Forks this conversation into a new one whose history is truncated
+    /// before the `nth_user_message`-th user message (zero-based).
+    #[napi]
+    pub async fn fork(&self, nth_user_message: u32) -> napi::Result<CodexSession> {
+        fork_session(
+            self.inner.manager.clone(),
+            self.inner.registry.clone(),
+            self.inner.config.clone(),
+            self.inner.rollout_path.clone(),
+            nth_user_message,
+        )
+        .await
+    }
+
+    /// Removes the conversation from the manager. With `graceful`, first submits
+    /// `Op::Shutdown` and waits up to `timeout_ms` for `ShutdownComplete` so
+    /// in-flight work and the rollout file can finish; events seen meanwhile stay
+    /// buffered for `next_event`.
+    #[napi]
+    pub async fn close(&self, options: Option<CloseOptions>) -> napi::Result<()> {
+        // Wait for an aborted subscription to finish so it cannot take events,
+        // `ShutdownComplete` included, from under `shutdown_and_wait`.
+        let subscription = self.inner.subscription.lock().unwrap().take();
+        if let Some(handle) = subscription {
+            handle.abort();
+            let _ = handle.await;
+        }
+
+        let (graceful, timeout_ms) = options
+            .map(|o| (o.graceful.unwrap_or(false), o.timeout_ms))
+            .unwrap_or((false, None));
+        if graceful {
+            let timeout = timeout_ms
+                .map(|ms| Duration::from_millis(ms as u64))
+                .unwrap_or(DEFAULT_SHUTDOWN_TIMEOUT);
+            // Best effort: on timeout or error we fall through to the hard drop.
+            let _ = tokio::time::timeout(timeout, self.inner.shutdown_and_wait()).await;
+        }
+
+        self.inner.deregister().await;
         Ok(())
     }
 }
@@ -87,27 +490,67 @@ impl CodexSession {
 #[napi]
 pub struct NativeCodex {
     manager: Arc<ConversationManager>,
+    auth_manager: Arc<AuthManager>,
+    auth_notifier: AuthNotifier,
+    sessions: Arc<SessionRegistry>,
+    codex_home: PathBuf,
 }
 
 #[napi]
 impl NativeCodex {
     #[napi(constructor)]
     pub fn new(options: Option<NativeCodexOptions>) -> napi::Result<Self> {
-        // Set the originator to identify as codex_cli_rs for consistent server-side treatment
-        std::env::set_var("CODEX_INTERNAL_ORIGINATOR_OVERRIDE", "codex_cli_rs");
+        let NativeCodexOptions {
+            codex_home,
+            session_source,
+            originator: requested_originator,
+        } = options.unwrap_or_default();
 
-        let codex_home = if let Some(opts) = options.and_then(|o| o.codex_home) {
-            std::path::PathBuf::from(opts)
-        } else {
-            config::find_codex_home()
-                .map_err(|err| napi::Error::from_reason(err.to_string()))?
+        // Identify as codex_cli_rs by default for consistent server-side treatment. The
+        // originator is process-wide in codex-core, so the first instance to set it wins.
+        let wanted = requested_originator.as_deref().unwrap_or(DEFAULT_ORIGINATOR);
+        match set_default_originator(wanted) {
+            Ok(()) => {}
+            Err(SetOriginatorError::InvalidHeaderValue) => {
+                return Err(native_error(
+                    "invalid_option",
+                    format!("originator `{wanted}` is not a valid header value"),
+                ));
+            }
+            Err(SetOriginatorError::AlreadyInitialized) => {
+                if requested_originator.is_some() && originator().value != wanted {
+                    return Err(native_error(
+                        "originator_conflict",
+                        format!(
+                            "originator is already set to `{}` for this process",
+                            originator().value
+                        ),
+                    ));
+                }
+            }
+        }
+
+        let session_source = match session_source {
+            Some(raw) => parse_enum::<SessionSource>(&raw).ok_or_else(|| {
+                native_error("invalid_option", format!("unknown session source `{raw}`"))
+            })?,
+            None => SessionSource::Mcp,
+        };
+
+        let codex_home = match codex_home {
+            Some(home) => PathBuf::from(home),
+            None => config::find_codex_home().map_err(config_error)?,
         };
 
-        let auth_manager = AuthManager::shared(codex_home, true);
-        let manager = ConversationManager::new(auth_manager, SessionSource::Mcp);
+        let auth_manager = AuthManager::shared(codex_home.clone(), true);
+        let manager = ConversationManager::new(auth_manager.clone(), session_source);
 
         Ok(Self {
             manager: Arc::new(manager),
+            auth_notifier: AuthNotifier::new(auth_manager.clone()),
+            auth_manager,
+            sessions: Arc::default(),
+            codex_home,
         })
     }
 
@@ -116,46 +559,396 @@ impl NativeCodex {
         &self,
         options: Option<CreateConversationOptions>,
     ) -> napi::Result<CodexSession> {
-        let overrides = match options.and_then(|o| o.overrides) {
-            Some(entries) => parse_overrides(entries)?,
-            None => Vec::new(),
-        };
+        let config = load_config(&self.codex_home, options).await?;
 
-        let config = Config::load_with_cli_overrides(overrides, ConfigOverrides::default())
+        let new_conversation = self
+            .manager
+            .new_conversation(config.clone())
             .await
-            .map_err(|err| napi::Error::from_reason(err.to_string()))?;
+            .map_err(codex_error)?;
+
+        Ok(new_session(
+            self.manager.clone(),
+            Arc::downgrade(&self.sessions),
+            config,
+            new_conversation,
+        ))
+    }
+
+    /// Resumes a conversation from a rollout file written by a previous session.
+    /// The first pending event is the resumed `SessionConfigured`, which carries
+    /// the replayed history in `initial_messages`.
+    #[napi]
+    pub async fn resume_conversation(
+        &self,
+        rollout_path: String,
+        options: Option<CreateConversationOptions>,
+    ) -> napi::Result<CodexSession> {
+        let config = load_config(&self.codex_home, options).await?;
 
         let new_conversation = self
             .manager
-            .new_conversation(config)
+            .resume_conversation_from_rollout(
+                config.clone(),
+                PathBuf::from(rollout_path),
+                self.auth_manager.clone(),
+            )
             .await
-            .map_err(|err| napi::Error::from_reason(err.to_string()))?;
+            .map_err(codex_error)?;
 
-        let session_configured_event = Event {
-            id: String::new(),
-            msg: EventMsg::SessionConfigured(new_conversation.session_configured),
-        };
+        Ok(new_session(
+            self.manager.clone(),
+            Arc::downgrade(&self.sessions),
+            config,
+            new_conversation,
+        ))
+    }
 
-        let inner = SessionInner {
-            conversation_id: new_conversation.conversation_id,
-            conversation: new_conversation.conversation,
-            manager: self.manager.clone(),
-            pending: Mutex::new(VecDeque::from([session_configured_event])),
-        };
+    /// Starts a new conversation from the rollout at `rollout_path`, keeping
+    /// only the history before the `nth_user_message`-th user message.
+    #[napi]
+    pub async fn fork_conversation(
+        &self,
+        rollout_path: String,
+        nth_user_message: u32,
+        options: Option<CreateConversationOptions>,
+    ) -> napi::Result<CodexSession> {
+        let config = load_config(&self.codex_home, options).await?;
+        fork_session(
+            self.manager.clone(),
+            Arc::downgrade(&self.sessions),
+            config,
+            PathBuf::from(rollout_path),
+            nth_user_message,
+        )
+        .await
+    }
+
+    /// Loads the config a conversation would get with `options` without
+    /// starting one. Secrets are redacted.
+    #[napi]
+    pub async fn resolve_config(
+        &self,
+        options: Option<CreateConversationOptions>,
+    ) -> napi::Result<EffectiveConfig> {
+        let config = load_config(&self.codex_home, options).await?;
+        effective_config(&config)
+    }
+
+    /// `apikey` or `chatgpt` for the credentials in this instance's codex_home,
+    /// or `null` when signed out.
+    #[napi]
+    pub fn get_auth_mode(&self) -> Option<String> {
+        self.auth_manager.auth().as_ref().and_then(auth_mode_name)
+    }
+
+    #[napi]
+    pub async fn auth_status(&self) -> napi::Result<AuthStatus> {
+        Ok(auth_status(self.auth_manager.auth()).await)
+    }
+
+    /// Writes `api_key` to this instance's `auth.json` and reloads credentials.
+    #[napi]
+    pub async fn login_with_api_key(&self, api_key: String) -> napi::Result<()> {
+        codex_core::auth::login_with_api_key(&self.codex_home, &api_key)
+            .map_err(|err| native_error("auth", err.to_string()))?;
+        self.auth_notifier.reload().await;
+        Ok(())
+    }
+
+    /// Refreshes the ChatGPT tokens now instead of waiting for a failed turn, and
+    /// resolves to the updated status.
+    #[napi]
+    pub async fn refresh_auth(&self) -> napi::Result<AuthStatus> {
+        self.auth_manager
+            .refresh_token()
+            .await
+            .map_err(|err| native_error("auth_refresh_failed", err.to_string()))?;
+        self.auth_notifier.notify().await;
+        Ok(auth_status(self.auth_manager.auth()).await)
+    }
+
+    /// Re-reads `auth.json`, e.g. after the CLI logged in. Resolves to `true`
+    /// when the credentials changed.
+    #[napi]
+    pub async fn reload_auth(&self) -> napi::Result<bool> {
+        Ok(self.auth_notifier.reload().await)
+    }
+
+    /// Calls `callback` with the new `AuthStatus` whenever this instance logs in,
+    /// logs out, reloads or refreshes credentials. Changes made outside the SDK,
+    /// such as a token refresh inside codex-core, are picked up by a background
+    /// check every 30 seconds. The callback does not keep the process alive.
+    /// Returns an id for `off_auth_change`.
+    #[napi]
+    pub fn on_auth_change(
+        &self,
+        callback: ThreadsafeFunction<AuthStatus, (), AuthStatus, napi::Status, true, true>,
+    ) -> u32 {
+        self.auth_notifier.subscribe(callback)
+    }
+
+    /// Removes a callback registered with `on_auth_change`. Returns `false` if
+    /// the id is unknown.
+    #[napi]
+    pub fn off_auth_change(&self, id: u32) -> bool {
+        self.auth_notifier.unsubscribe(id)
+    }
+
+    /// Starts the ChatGPT browser login callback server on a local port and returns
+    /// a handle with the authorization URL. Credentials are written to this
+    /// instance's codex_home. The device-code flow is not available in the
+    /// pinned codex-login.
+    #[napi]
+    pub async fn start_chatgpt_login(
+        &self,
+        options: Option<ChatGptLoginOptions>,
+    ) -> napi::Result<ChatGptLogin> {
+        start_chatgpt_login(self.codex_home.clone(), self.auth_notifier.clone(), options)
+    }
+
+    /// Removes stored credentials. Resolves to `false` if there were none.
+    #[napi]
+    pub async fn logout(&self) -> napi::Result<bool> {
+        let removed = self
+            .auth_manager
+            .logout()
+            .map_err(|err| native_error("auth", err.to_string()))?;
+        if removed {
+            self.auth_notifier.notify().await;
+        }
+        Ok(removed)
+    }
+
+    /// Pages through the rollout files under this instance's codex_home, newest
+    /// first.
+    #[napi]
+    pub async fn list_sessions(
+        &self,
+        options: Option<ListSessionsOptions>,
+    ) -> napi::Result<SessionsPage> {
+        list_sessions(&self.codex_home, options).await
+    }
+
+    /// Ids of the conversations opened through this instance that have not been
+    /// closed, shut down or seen their event stream end.
+    #[napi]
+    pub fn list_conversations(&self) -> Vec<String> {
+        self.sessions.sessions.lock().unwrap().keys().cloned().collect()
+    }
+
+    /// Returns a new handle to a live conversation, sharing its buffered events
+    /// and subscription with any other handle.
+    #[napi]
+    pub fn get_conversation(&self, conversation_id: String) -> Option<CodexSession> {
+        self.sessions
+            .sessions
+            .lock()
+            .unwrap()
+            .get(&conversation_id)
+            .map(|inner| CodexSession {
+                inner: inner.clone(),
+            })
+    }
+}
+
+fn new_session(
+    manager: Arc<ConversationManager>,
+    registry: Weak<SessionRegistry>,
+    config: Config,
+    new_conversation: NewConversation,
+) -> CodexSession {
+    let rollout_path = new_conversation.session_configured.rollout_path.clone();
+    let session_configured_event = Event {
+        id: String::new(),
+        msg: EventMsg::SessionConfigured(new_conversation.session_configured),
+    };
+
+    let inner = SessionInner {
+        conversation_id: new_conversation.conversation_id,
+        conversation: new_conversation.conversation,
+        manager,
+        config,
+        rollout_path,
+        pending: Mutex::new(VecDeque::from([session_configured_event])),
+        subscription: Mutex::new(None),
+        registry: registry.clone(),
+    };
+
+    let inner = Arc::new(inner);
+    if let Some(registry) = registry.upgrade() {
+        registry
+            .sessions
+            .lock()
+            .unwrap()
+            .insert(inner.conversation_id.to_string(), inner.clone());
+    }
+
+    CodexSession { inner }
+}
+
+async fn fork_session(
+    manager: Arc<ConversationManager>,
+    registry: Weak<SessionRegistry>,
+    config: Config,
+    rollout_path: PathBuf,
+    nth_user_message: u32,
+) -> napi::Result<CodexSession> {
+    let new_conversation = manager
+        .fork_conversation(nth_user_message as usize, config.clone(), rollout_path)
+        .await
+        .map_err(codex_error)?;
+
+    Ok(new_session(manager, registry, config, new_conversation))
+}
+
+/// Loads config from `codex_home` rather than `$CODEX_HOME`, so every instance
+/// reads its own `config.toml` and writes rollouts under its own home.
+async fn load_config(
+    codex_home: &Path,
+    options: Option<CreateConversationOptions>,
+) -> napi::Result<Config> {
+    let options = options.unwrap_or_default();
+    let entries = options.overrides.unwrap_or_default();
+    if options.strict_overrides.unwrap_or(false) {
+        validate_overrides(codex_home, &entries).await?;
+    }
+    let cli_overrides = parse_overrides(&entries);
 
-        Ok(CodexSession {
-            inner: Arc::new(inner),
+    let approval_policy = match options.approval_policy {
+        Some(raw) => Some(parse_enum::<AskForApproval>(&raw).ok_or_else(|| {
+            native_error("invalid_option", format!("unknown approval policy `{raw}`"))
+        })?),
+        None => None,
+    };
+    let sandbox_mode = match options.sandbox_mode {
+        Some(raw) => Some(parse_enum::<SandboxMode>(&raw).ok_or_else(|| {
+            native_error("invalid_option", format!("unknown sandbox mode `{raw}`"))
+        })?),
+        None => None,
+    };
+
+    let overrides = ConfigOverrides {
+        model: options.model,
+        cwd: options.cwd.map(PathBuf::from),
+        approval_policy,
+        sandbox_mode,
+        model_provider: options.model_provider,
+        config_profile: options.config_profile,
+        base_instructions: options.base_instructions,
+        include_plan_tool: options.include_plan_tool,
+        include_apply_patch_tool: options.include_apply_patch_tool,
+        include_view_image_tool: options.include_view_image_tool,
+        show_raw_agent_reasoning: options.show_raw_agent_reasoning,
+        ..Default::default()
+    };
+
+    let config_toml = config::load_config_as_toml_with_cli_overrides(codex_home, cli_overrides)
+        .await
+        .map_err(config_error)?;
+    Config::load_from_base_config_with_overrides(config_toml, overrides, codex_home.to_path_buf())
+        .map_err(config_error)
+}
+
+fn parse_overrides(entries: &[ConfigOverrideEntry]) -> Vec<(String, toml::Value)> {
+    entries
+        .iter()
+        .map(|entry| (entry.key.clone(), parse_override_value(&entry.value)))
+        .collect()
+}
+
+/// Values that are not valid TOML are read as strings, like `codex -c`.
+fn parse_override_value(raw: &str) -> toml::Value {
+    parse_toml_value(raw).unwrap_or_else(|| toml::Value::String(raw.to_string()))
+}
+
+/// Strict mode: applies each override on top of the loaded `config.toml` and
+/// deserializes the result as `ConfigToml`, so partial tables are checked with
+/// the fields they inherit. Values of the wrong type and unknown keys at any
+/// depth are reported per entry in one `invalid_overrides` error.
+async fn validate_overrides(codex_home: &Path, entries: &[ConfigOverrideEntry]) -> napi::Result<()> {
+    let base = config::load_config_as_toml_with_cli_overrides(codex_home, Vec::new())
+        .await
+        .map_err(config_error)?;
+    // A broken config.toml would fail every entry; let the real load report it.
+    if check_config_toml(base.clone()).is_err() {
+        return Ok(());
+    }
+
+    let failures: Vec<serde_json::Value> = entries
+        .iter()
+        .filter_map(|entry| {
+            let reason = check_override(&base, &entry.key, parse_override_value(&entry.value)).err()?;
+            let reason = if parse_toml_value(&entry.value).is_some() {
+                reason
+            } else {
+                format!("value is not valid TOML and was read as a string: {reason}")
+            };
+            Some(serde_json::json!({
+                "key": entry.key,
+                "value": entry.value,
+                "reason": reason,
+            }))
         })
+        .collect();
+
+    if !failures.is_empty() {
+        return Err(native_error_with_details(
+            "invalid_overrides",
+            format!("{} config override(s) failed validation", failures.len()),
+            serde_json::Value::Array(failures),
+        ));
     }
+    Ok(())
 }
 
-fn parse_overrides(entries: Vec<ConfigOverrideEntry>) -> napi::Result<Vec<(String, toml::Value)>> {
-    let mut result = Vec::with_capacity(entries.len());
-    for entry in entries {
-        let value = parse_toml_value(&entry.value).unwrap_or_else(|| toml::Value::String(entry.value));
-        result.push((entry.key, value));
+fn check_override(base: &toml::Value, key: &str, value: toml::Value) -> Result<(), String> {
+    let mut merged = base.clone();
+    apply_override(&mut merged, key, value);
+    let unknown = check_config_toml(merged)?;
+    // Unknown keys outside this override come from config.toml itself.
+    let is_related = |path: &String| {
+        path == key || path.starts_with(&format!("{key}.")) || key.starts_with(&format!("{path}."))
+    };
+    match unknown.iter().find(|path| is_related(path)) {
+        Some(path) => Err(format!("unknown config key `{path}`")),
+        None => Ok(()),
+    }
+}
+
+/// Deserializes `value` as `ConfigToml`, returning the dotted paths of every
+/// key it ignored.
+fn check_config_toml(value: toml::Value) -> Result<Vec<String>, String> {
+    let mut unknown = Vec::new();
+    serde_ignored::deserialize::<_, _, ConfigToml>(value, |path| {
+        // `Option` fields show up as `?` segments.
+        let path = path.to_string();
+        unknown.push(path.split('.').filter(|segment| *segment != "?").collect::<Vec<_>>().join("."));
+    })
+    .map_err(|err| err.to_string())?;
+    Ok(unknown)
+}
+
+/// Sets a dotted `key` the way codex-core applies `-c` overrides, replacing
+/// any non-table value on the way.
+fn apply_override(root: &mut toml::Value, key: &str, value: toml::Value) {
+    let mut current = root;
+    let mut segments = key.split('.').peekable();
+    while let Some(segment) = segments.next() {
+        if !current.is_table() {
+            *current = toml::Value::Table(toml::Table::new());
+        }
+        let Some(table) = current.as_table_mut() else {
+            return;
+        };
+        if segments.peek().is_none() {
+            table.insert(segment.to_string(), value);
+            return;
+        }
+        current = table
+            .entry(segment)
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()));
     }
-    Ok(result)
 }
 
 fn parse_toml_value(raw: &str) -> Option<toml::Value> {
@@ -165,9 +958,43 @@ fn parse_toml_value(raw: &str) -> Option<toml::Value> {
         .and_then(|value| value.get("_x_").cloned())
 }
 
+fn to_input_item(item: UserInputItem) -> napi::Result<InputItem> {
+    let missing = |field: &str| {
+        native_error(
+            "invalid_input",
+            format!("`{}` input item requires `{field}`", item.item_type),
+        )
+    };
+    match item.item_type.as_str() {
+        "text" => Ok(InputItem::Text {
+            text: item.text.clone().ok_or_else(|| missing("text"))?,
+        }),
+        "image" => Ok(InputItem::Image {
+            image_url: item.image_url.clone().ok_or_else(|| missing("imageUrl"))?,
+        }),
+        "local_image" => Ok(InputItem::LocalImage {
+            path: PathBuf::from(item.path.clone().ok_or_else(|| missing("path"))?),
+        }),
+        other => Err(native_error(
+            "invalid_input",
+            format!("unknown input item type `{other}`"),
+        )),
+    }
+}
+
+fn parse_review_decision(raw: &str) -> napi::Result<ReviewDecision> {
+    parse_enum(raw)
+        .ok_or_else(|| native_error("invalid_decision", format!("unknown review decision `{raw}`")))
+}
+
+/// Parses a value from its serde string form, e.g. `workspace-write`.
+fn parse_enum<T: serde::de::DeserializeOwned>(raw: &str) -> Option<T> {
+    serde_json::from_value(serde_json::Value::String(raw.to_string())).ok()
+}
+
 fn serialize_event(event: Event) -> napi::Result<String> {
     // Serialize the event directly - rate limits come from actual API responses
-    serde_json::to_string(&event).map_err(|err| napi::Error::from_reason(err.to_string()))
+    serde_json::to_string(&event).map_err(|err| native_error("serialization", err.to_string()))
 }
 
 fn resolved_version() -> &'static str {
@@ -185,3 +1012,103 @@ pub fn version() -> String {
 pub fn cli_version() -> String {
     resolved_version().to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(key: &str, value: &str) -> ConfigOverrideEntry {
+        ConfigOverrideEntry {
+            key: key.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    fn base() -> toml::Value {
+        toml::from_str(
+            r#"
+            model = "gpt-5-codex"
+
+            [mcp_servers.docs]
+            command = "docs-server"
+            "#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn parse_overrides_falls_back_to_strings() {
+        let parsed = parse_overrides(&[
+            entry("model", "o3"),
+            entry("model_context_window", "4096"),
+            entry("sandbox_workspace_write.network_access", "true"),
+        ]);
+        assert_eq!(
+            parsed,
+            vec![
+                ("model".to_string(), toml::Value::String("o3".to_string())),
+                ("model_context_window".to_string(), toml::Value::Integer(4096)),
+                (
+                    "sandbox_workspace_write.network_access".to_string(),
+                    toml::Value::Boolean(true)
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn apply_override_creates_and_replaces_tables() {
+        let mut root = toml::from_str::<toml::Value>("tui = 1").unwrap();
+        apply_override(&mut root, "tui.notifications", toml::Value::Boolean(true));
+        apply_override(&mut root, "model", toml::Value::String("o3".to_string()));
+        assert_eq!(
+            root,
+            toml::from_str::<toml::Value>("model = \"o3\"\n[tui]\nnotifications = true").unwrap()
+        );
+    }
+
+    #[test]
+    fn check_override_accepts_partial_tables() {
+        let base = base();
+        assert_eq!(
+            check_override(&base, "mcp_servers.docs.env.TOKEN", toml::Value::String("x".to_string())),
+            Ok(())
+        );
+        assert_eq!(
+            check_override(&base, "sandbox_workspace_write.network_access", toml::Value::Boolean(true)),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn check_override_rejects_unknown_nested_keys() {
+        let err = check_override(
+            &base(),
+            "sandbox_workspace_write.netwrok_access",
+            toml::Value::Boolean(true),
+        )
+        .unwrap_err();
+        assert_eq!(err, "unknown config key `sandbox_workspace_write.netwrok_access`");
+
+        let err = check_override(&base(), "modle", toml::Value::String("o3".to_string())).unwrap_err();
+        assert_eq!(err, "unknown config key `modle`");
+    }
+
+    #[test]
+    fn check_override_rejects_wrong_types() {
+        let err = check_override(
+            &base(),
+            "sandbox_workspace_write.network_access",
+            parse_override_value("tru"),
+        )
+        .unwrap_err();
+        assert!(err.contains("sandbox_workspace_write.network_access"), "{err}");
+    }
+
+    #[test]
+    fn check_override_ignores_unknown_keys_from_config_toml() {
+        let mut base = base();
+        apply_override(&mut base, "legacy_key", toml::Value::Boolean(true));
+        assert_eq!(check_override(&base, "model", toml::Value::String("o3".to_string())), Ok(()));
+    }
+}
@@ -0,0 +1,88 @@
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use codex_login::{run_login_server, LoginServer, ServerOptions, ShutdownHandle};
+use napi_derive::napi;
+
+use crate::auth::AuthNotifier;
+use crate::error::native_error;
+
+#[napi(object)]
+pub struct ChatGptLoginOptions {
+    /// Local port for the OAuth callback server; defaults to codex-login's port (1455).
+    pub port: Option<u32>,
+    /// Open the system browser on the authorization URL. Defaults to `false`,
+    /// since the SDK usually runs headless and relays the URL to a web UI.
+    pub open_browser: Option<bool>,
+}
+
+/// A running ChatGPT browser login. Show `auth_url` to the user, then await
+/// `wait_for_completion`, which resolves once `auth.json` has been written.
+#[napi]
+pub struct ChatGptLogin {
+    auth_url: String,
+    port: u16,
+    server: Mutex<Option<LoginServer>>,
+    shutdown: ShutdownHandle,
+    auth_notifier: AuthNotifier,
+}
+
+#[napi]
+impl ChatGptLogin {
+    #[napi(getter)]
+    pub fn auth_url(&self) -> String {
+        self.auth_url.clone()
+    }
+
+    #[napi(getter)]
+    pub fn port(&self) -> u32 {
+        self.port as u32
+    }
+
+    #[napi]
+    pub async fn wait_for_completion(&self) -> napi::Result<()> {
+        let server = self.server.lock().unwrap().take().ok_or_else(|| {
+            native_error("login_in_progress", "login is already being awaited")
+        })?;
+        server
+            .block_until_done()
+            .await
+            .map_err(|err| native_error("login_failed", err.to_string()))?;
+        self.auth_notifier.reload().await;
+        Ok(())
+    }
+
+    /// Stops the callback server; a pending `wait_for_completion` rejects.
+    #[napi]
+    pub fn cancel(&self) {
+        self.shutdown.shutdown();
+    }
+}
+
+pub(crate) fn start_chatgpt_login(
+    codex_home: PathBuf,
+    auth_notifier: AuthNotifier,
+    options: Option<ChatGptLoginOptions>,
+) -> napi::Result<ChatGptLogin> {
+    let mut server_options =
+        ServerOptions::new(codex_home, codex_core::auth::CLIENT_ID.to_string());
+    server_options.open_browser = false;
+    if let Some(options) = options {
+        if let Some(port) = options.port {
+            server_options.port = u16::try_from(port)
+                .map_err(|_| native_error("invalid_option", format!("invalid port {port}")))?;
+        }
+        server_options.open_browser = options.open_browser.unwrap_or(false);
+    }
+
+    let server = run_login_server(server_options)
+        .map_err(|err| native_error("login_failed", err.to_string()))?;
+
+    Ok(ChatGptLogin {
+        auth_url: server.auth_url.clone(),
+        port: server.actual_port,
+        shutdown: server.cancel_handle(),
+        server: Mutex::new(Some(server)),
+        auth_notifier,
+    })
+}
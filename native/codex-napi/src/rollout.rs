@@ -0,0 +1,289 @@
+use std::path::{Path, PathBuf};
+
+use codex_core::{ConversationItem, Cursor, RolloutRecorder};
+use codex_protocol::protocol::{RolloutItem, RolloutLine, SessionMetaLine, SessionSource};
+use napi_derive::napi;
+use serde_json::Value as JsonValue;
+
+use crate::error::{native_error, native_error_with_details};
+use crate::parse_enum;
+
+const DEFAULT_PAGE_SIZE: u32 = 25;
+const PREVIEW_MAX_CHARS: usize = 200;
+
+#[napi(object)]
+pub struct ListSessionsOptions {
+    pub page_size: Option<u32>,
+    /// Opaque `next_cursor` from a previous page.
+    pub cursor: Option<String>,
+    /// Only include sessions from these sources (`cli`, `vscode`, `exec`, `mcp`);
+    /// all sources when omitted.
+    pub sources: Option<Vec<String>>,
+}
+
+#[napi(object)]
+pub struct SessionSummary {
+    pub session_id: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+    pub cwd: Option<String>,
+    pub source: Option<String>,
+    /// First user message, truncated.
+    pub preview: Option<String>,
+    pub rollout_path: String,
+}
+
+#[napi(object)]
+pub struct SessionsPage {
+    pub sessions: Vec<SessionSummary>,
+    pub next_cursor: Option<String>,
+    /// True when the scan stopped early; more sessions may exist past `next_cursor`.
+    pub reached_scan_cap: bool,
+}
+
+#[napi(object)]
+pub struct RolloutSessionMeta {
+    pub id: String,
+    pub timestamp: String,
+    pub cwd: String,
+    pub originator: String,
+    pub cli_version: String,
+    pub instructions: Option<String>,
+    pub source: Option<String>,
+    pub git: Option<JsonValue>,
+}
+
+/// Rollout records after the session meta, split by kind. Each list keeps file
+/// order; the payloads are the `codex_protocol` types serialized as-is.
+#[napi(object)]
+pub struct RolloutContents {
+    pub session_meta: Option<RolloutSessionMeta>,
+    #[napi(ts_type = "Array<ResponseItem>")]
+    pub response_items: Vec<JsonValue>,
+    #[napi(ts_type = "Array<EventMsg>")]
+    pub event_msgs: Vec<JsonValue>,
+    #[napi(ts_type = "Array<TurnContextItem>")]
+    pub turn_contexts: Vec<JsonValue>,
+    #[napi(ts_type = "Array<CompactedItem>")]
+    pub compacted: Vec<JsonValue>,
+}
+
+/// Parses a rollout JSONL file with the pinned `codex_protocol` types, so the
+/// format always matches the codex-rs version this module was built against.
+/// Rejects with `io` when the file cannot be read and `invalid_rollout` (with
+/// the 1-based `line` in `details`) when a record does not parse.
+#[napi]
+pub async fn read_rollout(path: String) -> napi::Result<RolloutContents> {
+    let text = tokio::fs::read_to_string(&path)
+        .await
+        .map_err(|err| native_error("io", format!("failed to read {path}: {err}")))?;
+
+    let mut contents = RolloutContents {
+        session_meta: None,
+        response_items: Vec::new(),
+        event_msgs: Vec::new(),
+        turn_contexts: Vec::new(),
+        compacted: Vec::new(),
+    };
+    for (index, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record: RolloutLine = serde_json::from_str(line).map_err(|err| {
+            native_error_with_details(
+                "invalid_rollout",
+                format!("line {} of {path} is not a valid rollout record: {err}", index + 1),
+                serde_json::json!({ "line": index + 1 }),
+            )
+        })?;
+        let (list, value) = match record.item {
+            // Forked rollouts repeat the parent's meta; the first one describes this file.
+            RolloutItem::SessionMeta(line) => {
+                if contents.session_meta.is_none() {
+                    contents.session_meta = Some(to_session_meta(line));
+                }
+                continue;
+            }
+            RolloutItem::ResponseItem(item) => (&mut contents.response_items, serde_json::to_value(item)),
+            RolloutItem::EventMsg(msg) => (&mut contents.event_msgs, serde_json::to_value(msg)),
+            RolloutItem::TurnContext(context) => (&mut contents.turn_contexts, serde_json::to_value(context)),
+            RolloutItem::Compacted(compacted) => (&mut contents.compacted, serde_json::to_value(compacted)),
+        };
+        list.push(value.map_err(|err| native_error("serialization", err.to_string()))?);
+    }
+    Ok(contents)
+}
+
+fn to_session_meta(line: SessionMetaLine) -> RolloutSessionMeta {
+    RolloutSessionMeta {
+        id: line.meta.id.to_string(),
+        timestamp: line.meta.timestamp,
+        cwd: line.meta.cwd.display().to_string(),
+        originator: line.meta.originator,
+        cli_version: line.meta.cli_version,
+        instructions: line.meta.instructions,
+        source: serde_json::to_value(&line.meta.source)
+            .ok()
+            .and_then(|value| value.as_str().map(str::to_string)),
+        git: line.git.and_then(|git| serde_json::to_value(git).ok()),
+    }
+}
+
+pub(crate) async fn list_sessions(
+    codex_home: &Path,
+    options: Option<ListSessionsOptions>,
+) -> napi::Result<SessionsPage> {
+    let (page_size, cursor, sources) = match options {
+        Some(o) => (o.page_size, o.cursor, o.sources),
+        None => (None, None, None),
+    };
+
+    let cursor = match cursor {
+        Some(token) => Some(
+            parse_enum::<Cursor>(&token)
+                .ok_or_else(|| native_error("invalid_cursor", format!("invalid cursor `{token}`")))?,
+        ),
+        None => None,
+    };
+    let sources = sources
+        .unwrap_or_default()
+        .into_iter()
+        .map(|raw| {
+            parse_enum::<SessionSource>(&raw).ok_or_else(|| {
+                native_error("invalid_option", format!("unknown session source `{raw}`"))
+            })
+        })
+        .collect::<napi::Result<Vec<_>>>()?;
+
+    let page = RolloutRecorder::list_conversations(
+        codex_home,
+        page_size.unwrap_or(DEFAULT_PAGE_SIZE).max(1) as usize,
+        cursor.as_ref(),
+        &sources,
+    )
+    .await
+    .map_err(|err| native_error("io", err.to_string()))?;
+
+    // Stat the whole page in one blocking task rather than on a runtime worker.
+    let paths: Vec<PathBuf> = page.items.iter().map(|item| item.path.clone()).collect();
+    let updated_at = tokio::task::spawn_blocking(move || {
+        paths.iter().map(|path| modified_at(path)).collect::<Vec<_>>()
+    })
+    .await
+    .map_err(|err| native_error("io", err.to_string()))?;
+
+    Ok(SessionsPage {
+        sessions: page
+            .items
+            .into_iter()
+            .zip(updated_at)
+            .map(|(item, updated_at)| to_session_summary(item, updated_at))
+            .collect(),
+        next_cursor: page
+            .next_cursor
+            .and_then(|cursor| serde_json::to_value(cursor).ok())
+            .and_then(|value| value.as_str().map(str::to_string)),
+        reached_scan_cap: page.reached_scan_cap,
+    })
+}
+
+/// `head` holds the raw JSON of the first rollout records: the session meta
+/// followed by response items.
+fn to_session_summary(item: ConversationItem, updated_at: Option<String>) -> SessionSummary {
+    let meta = item.head.first();
+    let meta_str = |key: &str| {
+        meta.and_then(|m| m.get(key))
+            .and_then(JsonValue::as_str)
+            .map(str::to_string)
+    };
+
+    SessionSummary {
+        session_id: meta_str("id"),
+        created_at: meta_str("timestamp"),
+        updated_at,
+        cwd: meta_str("cwd"),
+        source: meta_str("source"),
+        preview: item.head.iter().find_map(user_message_text).map(truncate_preview),
+        rollout_path: item.path.display().to_string(),
+    }
+}
+
+/// Rollouts are append-only, so the file's mtime is the last activity.
+fn modified_at(path: &Path) -> Option<String> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .map(|modified| chrono::DateTime::<chrono::Utc>::from(modified).to_rfc3339())
+}
+
+fn user_message_text(item: &JsonValue) -> Option<String> {
+    if item.get("type").and_then(JsonValue::as_str) != Some("message")
+        || item.get("role").and_then(JsonValue::as_str) != Some("user")
+    {
+        return None;
+    }
+    item.get("content")?
+        .as_array()?
+        .iter()
+        .filter(|part| part.get("type").and_then(JsonValue::as_str) == Some("input_text"))
+        .filter_map(|part| part.get("text").and_then(JsonValue::as_str))
+        // Skip the injected AGENTS.md and environment context messages.
+        .find(|text| !text.starts_with("<user_instructions>") && !text.starts_with("<environment_context>"))
+        .map(str::to_string)
+}
+
+fn truncate_preview(text: String) -> String {
+    match text.char_indices().nth(PREVIEW_MAX_CHARS) {
+        Some((idx, _)) => format!("{}…", &text[..idx]),
+        None => text,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn user_message_text_skips_injected_context() {
+        let item = json!({
+            "type": "message",
+            "role": "user",
+            "content": [
+                { "type": "input_text", "text": "<user_instructions>be terse</user_instructions>" },
+                { "type": "input_text", "text": "<environment_context>cwd</environment_context>" },
+                { "type": "input_image", "image_url": "data:" },
+                { "type": "input_text", "text": "fix the build" }
+            ]
+        });
+        assert_eq!(user_message_text(&item).as_deref(), Some("fix the build"));
+    }
+
+    #[test]
+    fn user_message_text_ignores_other_items() {
+        let assistant = json!({
+            "type": "message",
+            "role": "assistant",
+            "content": [{ "type": "output_text", "text": "done" }]
+        });
+        let context_only = json!({
+            "type": "message",
+            "role": "user",
+            "content": [{ "type": "input_text", "text": "<environment_context>cwd</environment_context>" }]
+        });
+        assert_eq!(user_message_text(&assistant), None);
+        assert_eq!(user_message_text(&context_only), None);
+        assert_eq!(user_message_text(&json!({ "type": "session_meta" })), None);
+    }
+
+    #[test]
+    fn truncate_preview_counts_chars() {
+        assert_eq!(truncate_preview("short".to_string()), "short");
+
+        let exact = "é".repeat(PREVIEW_MAX_CHARS);
+        assert_eq!(truncate_preview(exact.clone()), exact);
+
+        let long = "é".repeat(PREVIEW_MAX_CHARS + 1);
+        assert_eq!(truncate_preview(long), format!("{}…", "é".repeat(PREVIEW_MAX_CHARS)));
+    }
+}
@@ -63,9 +63,16 @@ import {
   type NativeCodexInstance,
   type CodexSessionHandle,
   formatOverrides,
+  normalizeNativeError,
 } from '../internal/nativeModule';
 import { AsyncEventQueue } from '../internal/AsyncEventQueue';
-import { CodexConnectionError, CodexError, CodexSessionError } from '../errors/CodexError';
+import {
+  CodexAuthError,
+  CodexConnectionError,
+  CodexError,
+  CodexSessionError,
+  isNativeAuthError,
+} from '../errors/CodexError';
 import type { PartialCodexLogger } from '../utils/logger';
 import { log } from '../utils/logger';
 import { withRetry } from '../utils/retry';
@@ -681,6 +688,7 @@ export class CodexClient extends EventEmitter {
           try {
             payload = await session.nextEvent();
           } catch (error) {
+            normalizeNativeError(error);
             this.emit('error', error);
             await this.dispatchOnError(error);
             break;
@@ -1010,18 +1018,28 @@ export class CodexClient extends EventEmitter {
     return expandHomePath(configured);
   }
 
-  private wrapConnectionError(message: string, cause: unknown, codexHome?: string): CodexConnectionError {
-    return new CodexConnectionError(message, {
+  private wrapConnectionError(message: string, cause: unknown, codexHome?: string): CodexError {
+    const native = normalizeNativeError(cause);
+    const details = {
       cause: cause instanceof Error ? cause.message : String(cause),
       codexHome: codexHome ?? this.config.codexHome ?? process.env.CODEX_HOME,
-    });
+    };
+    if (isNativeAuthError(native)) {
+      return new CodexAuthError(message, details, native);
+    }
+    return new CodexConnectionError(message, details, native);
   }
 
-  private wrapSessionError(message: string, cause: unknown, details?: unknown): CodexSessionError {
-    return new CodexSessionError(message, {
+  private wrapSessionError(message: string, cause: unknown, details?: unknown): CodexError {
+    const native = normalizeNativeError(cause);
+    const payload = {
       cause: errorMessage(cause),
       details,
-    });
+    };
+    if (isNativeAuthError(native)) {
+      return new CodexAuthError(message, payload, native);
+    }
+    return new CodexSessionError(message, payload, native);
   }
 }
 
@@ -1,29 +1,63 @@
+/** Stable error fields reported by the native bindings. */
+export interface NativeErrorInfo {
+  /** e.g. `unauthorized`, `usage_limit_reached`, `sandbox_denied`, `config_parse`. */
+  code: string;
+  /** HTTP status, for `unauthorized` and `unexpected_status`. */
+  statusCode?: number;
+  /** Suggested wait before retrying, for `stream_error` and `usage_limit_reached`. */
+  retryAfterMs?: number;
+  details?: unknown;
+}
+
+/** Native codes that mean the credentials must be refreshed or replaced. */
+const NATIVE_AUTH_CODES: ReadonlySet<string> = new Set(['unauthorized', 'usage_not_included']);
+
 export class CodexError extends Error {
   readonly code: string;
   readonly details?: unknown;
+  /** Set when the error was raised by the native bindings. */
+  readonly native?: NativeErrorInfo;
 
-  constructor(message: string, code: string, details?: unknown) {
+  constructor(message: string, code: string, details?: unknown, native?: NativeErrorInfo) {
     super(message);
     this.name = new.target.name;
     this.code = code;
     this.details = details;
+    this.native = native;
+  }
+
+  /** Native error code, e.g. `usage_limit_reached`, when raised by the native bindings. */
+  get nativeCode(): string | undefined {
+    return this.native?.code;
+  }
+
+  get statusCode(): number | undefined {
+    return this.native?.statusCode;
+  }
+
+  get retryAfterMs(): number | undefined {
+    return this.native?.retryAfterMs;
   }
 }
 
 export class CodexAuthError extends CodexError {
-  constructor(message: string, details?: unknown) {
-    super(message, 'AUTH', details);
+  constructor(message: string, details?: unknown, native?: NativeErrorInfo) {
+    super(message, 'AUTH', details, native);
   }
 }
 
 export class CodexConnectionError extends CodexError {
-  constructor(message: string, details?: unknown) {
-    super(message, 'CONNECTION', details);
+  constructor(message: string, details?: unknown, native?: NativeErrorInfo) {
+    super(message, 'CONNECTION', details, native);
   }
 }
 
 export class CodexSessionError extends CodexError {
-  constructor(message: string, details?: unknown) {
-    super(message, 'SESSION', details);
+  constructor(message: string, details?: unknown, native?: NativeErrorInfo) {
+    super(message, 'SESSION', details, native);
   }
 }
+
+export function isNativeAuthError(native: NativeErrorInfo | undefined): boolean {
+  return native !== undefined && NATIVE_AUTH_CODES.has(native.code);
+}
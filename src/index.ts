@@ -68,7 +68,8 @@ export { ConversationResumer } from './persistence/ConversationResumer';
 export { DataStorage } from './monitoring/DataStorage';
 export { MockDataGenerator } from './monitoring/MockDataGenerator';
 
-export { CodexError, CodexAuthError, CodexConnectionError, CodexSessionError } from './errors/CodexError';
+export { CodexError, CodexAuthError, CodexConnectionError, CodexSessionError, isNativeAuthError } from './errors/CodexError';
+export type { NativeErrorInfo } from './errors/CodexError';
 
 // Conversation management types
 export type {
@@ -2,6 +2,7 @@ import { existsSync } from 'fs';
 import * as path from 'path';
 import { fileURLToPath, pathToFileURL } from 'url';
 import { createRequire } from 'module';
+import type { NativeErrorInfo } from '../errors/CodexError';
 import type { PartialCodexLogger } from '../utils/logger';
 import { log } from '../utils/logger';
 
@@ -191,3 +192,48 @@ export function formatOverrides(overrides?: Record<string, string>): ConfigOverr
   }
   return entries;
 }
+
+const normalizedErrors = new WeakMap<Error, NativeErrorInfo>();
+
+/**
+ * Native errors keep a plain-text `message` and carry their stable `code` and
+ * structured fields as JSON in `cause.message`, because napi-rs can only reject
+ * async calls with a plain `Error`. Moves those fields onto the error itself
+ * (`code`, `statusCode`, `retryAfterMs`, `details`) and drops the `cause`.
+ * Returns the fields, or `undefined` for errors that did not come from the
+ * native module. Safe to call more than once on the same error.
+ */
+export function normalizeNativeError(error: unknown): NativeErrorInfo | undefined {
+  if (!(error instanceof Error)) {
+    return undefined;
+  }
+  const cached = normalizedErrors.get(error);
+  if (cached) {
+    return cached;
+  }
+
+  const cause = (error as { cause?: unknown }).cause;
+  if (!(cause instanceof Error)) {
+    return undefined;
+  }
+  let info: NativeErrorInfo;
+  try {
+    const parsed = JSON.parse(cause.message) as Partial<NativeErrorInfo> | null;
+    if (!parsed || typeof parsed.code !== 'string') {
+      return undefined;
+    }
+    info = {
+      code: parsed.code,
+      ...(typeof parsed.statusCode === 'number' ? { statusCode: parsed.statusCode } : {}),
+      ...(typeof parsed.retryAfterMs === 'number' ? { retryAfterMs: parsed.retryAfterMs } : {}),
+      ...(parsed.details !== undefined ? { details: parsed.details } : {}),
+    };
+  } catch {
+    return undefined;
+  }
+
+  delete (error as { cause?: unknown }).cause;
+  Object.assign(error, info);
+  normalizedErrors.set(error, info);
+  return info;
+}
@@ -14,7 +14,7 @@ import {
 import type { CodexClientConfig } from '../src/types/options';
 import type { CodexPlugin } from '../src/plugins/types';
 import { CodexClient } from '../src/client/CodexClient';
-import { CodexConnectionError, CodexSessionError } from '../src/errors/CodexError';
+import { CodexAuthError, CodexConnectionError, CodexSessionError } from '../src/errors/CodexError';
 import * as retryModule from '../src/utils/retry';
 import type {
   CodexSessionHandle,
@@ -684,6 +684,16 @@ describe('CodexClient advanced behaviour', () => {
     const sessionErrWithError = internal.wrapSessionError('boom', new Error('nope'), { reason: 'bad' });
     expect(sessionErrWithError.details).toMatchObject({ cause: 'nope', details: { reason: 'bad' } });
 
+    const nativeAuthErr = internal.wrapSessionError(
+      'denied',
+      new Error('unexpected status 401', {
+        cause: new Error(JSON.stringify({ code: 'unauthorized', statusCode: 401 })),
+      }),
+    );
+    expect(nativeAuthErr).toBeInstanceOf(CodexAuthError);
+    expect(nativeAuthErr.details).toMatchObject({ cause: 'unexpected status 401' });
+    expect(nativeAuthErr.native).toEqual({ code: 'unauthorized', statusCode: 401 });
+
     const idA = internal.generateRequestId();
     const idB = internal.generateRequestId();
     expect(idB).not.toEqual(idA);
@@ -1,5 +1,11 @@
 import { describe, expect, it } from 'vitest';
-import { CodexAuthError, CodexConnectionError, CodexError, CodexSessionError } from '../../src/errors/CodexError';
+import {
+  CodexAuthError,
+  CodexConnectionError,
+  CodexError,
+  CodexSessionError,
+  isNativeAuthError,
+} from '../../src/errors/CodexError';
 
 describe('CodexError hierarchy', () => {
   it('preserves message, code, and details', () => {
@@ -15,4 +21,18 @@ describe('CodexError hierarchy', () => {
     expect(new CodexConnectionError('conn', { codexHome: '/tmp' }).details).toEqual({ codexHome: '/tmp' });
     expect(new CodexSessionError('session').code).toBe('SESSION');
   });
+
+  it('exposes native error fields', () => {
+    const err = new CodexSessionError('failed', undefined, { code: 'unexpected_status', statusCode: 502 });
+    expect(err.nativeCode).toBe('unexpected_status');
+    expect(err.statusCode).toBe(502);
+    expect(err.retryAfterMs).toBeUndefined();
+    expect(new CodexSessionError('failed').nativeCode).toBeUndefined();
+  });
+
+  it('classifies native auth codes', () => {
+    expect(isNativeAuthError({ code: 'unauthorized', statusCode: 401 })).toBe(true);
+    expect(isNativeAuthError({ code: 'sandbox_denied' })).toBe(false);
+    expect(isNativeAuthError(undefined)).toBe(false);
+  });
 });
@@ -107,4 +107,27 @@ describe('nativeModule utilities', () => {
       { key: 'summary', value: 'brief' },
     ]);
   });
+
+  it('moves native error fields from the cause onto the error', async () => {
+    const { normalizeNativeError } = await importNativeModule();
+    const error = new Error('usage limit reached', {
+      cause: new Error(JSON.stringify({ code: 'usage_limit_reached', retryAfterMs: 60000 })),
+    });
+
+    expect(normalizeNativeError(error)).toEqual({ code: 'usage_limit_reached', retryAfterMs: 60000 });
+    expect(error.message).toBe('usage limit reached');
+    expect(error).toMatchObject({ code: 'usage_limit_reached', retryAfterMs: 60000 });
+    expect(error.cause).toBeUndefined();
+    expect(normalizeNativeError(error)).toEqual({ code: 'usage_limit_reached', retryAfterMs: 60000 });
+  });
+
+  it('leaves errors without a native payload untouched', async () => {
+    const { normalizeNativeError } = await importNativeModule();
+    const plainCause = new Error('outer', { cause: new Error('not json') });
+
+    expect(normalizeNativeError('boom')).toBeUndefined();
+    expect(normalizeNativeError(new Error('plain'))).toBeUndefined();
+    expect(normalizeNativeError(plainCause)).toBeUndefined();
+    expect(plainCause.cause).toBeInstanceOf(Error);
+  });
 });